@@ -0,0 +1,234 @@
+use bevy::{prelude::*, transform::TransformSystems};
+use std::{f32::consts::PI, time::Duration};
+
+use super::input::{LookInput, LookInputReader};
+use crate::prelude::{LinearStepper, PitchYawClamped, SpringStepper, TickInterpolator};
+
+/// How the look direction follows the accumulated input
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookSmoothing {
+    /// Follow input immediately
+    None,
+    /// Turn towards the input at a maximum angular speed
+    Linear(LinearStepper<PitchYawClamped>),
+    /// Follow the input through a spring-damper system
+    Spring(SpringStepper<PitchYawClamped, Vec2>),
+}
+
+/// First-person mouse-look, placed on the body entity.
+///
+/// Yaw is written to the body's [`Transform`] and pitch to the `head` entity's
+/// [`Transform`], which is expected to be a child of the body (typically the camera).
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct FirstPersonLook {
+    /// Look direction accumulated from input. Yaw is not clamped by default, so that
+    /// smoothing never has to cross the yaw wrapping boundary.
+    pub target: PitchYawClamped,
+    pub smoothing: LookSmoothing,
+    pub input: LookInput,
+    /// Whether to read mouse and gamepad input. Disable to drive `target` manually.
+    pub input_enabled: bool,
+    /// Entity that receives the pitch rotation
+    pub head: Option<Entity>,
+}
+
+impl Default for FirstPersonLook {
+    fn default() -> Self {
+        Self {
+            target: PitchYawClamped::new_with_clamps(0., 0., PI / 2. - 0.01, f32::MAX),
+            smoothing: LookSmoothing::None,
+            input: LookInput::default(),
+            input_enabled: true,
+            head: None,
+        }
+    }
+}
+
+impl FirstPersonLook {
+    pub fn new(head: Entity) -> Self {
+        Self {
+            head: Some(head),
+            ..default()
+        }
+    }
+
+    pub fn with_linear_smoothing(mut self, speed: f32) -> Self {
+        self.smoothing = LookSmoothing::Linear(LinearStepper::new(self.target, speed));
+        self
+    }
+
+    pub fn with_spring_smoothing(mut self, spring: f32, damping: f32) -> Self {
+        self.smoothing = LookSmoothing::Spring(SpringStepper::new(self.target, spring, damping));
+        self
+    }
+
+    /// Rotates the target look direction by `delta` (yaw in `x`, pitch in `y`)
+    pub fn rotate(&mut self, delta: Vec2) {
+        self.target = (self.target + PitchYawClamped::new(delta.x, delta.y)).normalize();
+    }
+
+    /// Current, smoothed, look direction
+    pub fn look(&self) -> PitchYawClamped {
+        match &self.smoothing {
+            LookSmoothing::None => self.target,
+            LookSmoothing::Linear(stepper) => stepper.get(),
+            LookSmoothing::Spring(stepper) => stepper.get(),
+        }
+    }
+
+    pub fn tick(&mut self, dt: Duration) {
+        let target = self.target;
+        match &mut self.smoothing {
+            LookSmoothing::None => {}
+            LookSmoothing::Linear(stepper) => {
+                stepper.set_target(target);
+                stepper.tick(dt);
+            }
+            LookSmoothing::Spring(stepper) => {
+                stepper.set_target(target);
+                stepper.tick(dt);
+            }
+        }
+        self.rewrap_yaw();
+    }
+
+    /// Keeps the unclamped yaw from growing without bound, shifting the target and the
+    /// smoothed value together so that the smoothing is unaffected
+    fn rewrap_yaw(&mut self) {
+        let shift = yaw_rewrap_shift(self.target.y);
+        if shift == 0. {
+            return;
+        }
+        self.target.y -= shift;
+        match &mut self.smoothing {
+            LookSmoothing::None => {}
+            LookSmoothing::Linear(stepper) => {
+                stepper.current.y -= shift;
+                stepper.target.y -= shift;
+            }
+            LookSmoothing::Spring(stepper) => {
+                stepper.current.y -= shift;
+                stepper.target.y -= shift;
+            }
+        }
+    }
+}
+
+/// Whole turns to subtract from an unclamped yaw to bring it back near zero. Zero until
+/// the yaw is at least two turns out, so that the shift doesn't happen every frame
+pub(super) fn yaw_rewrap_shift(yaw: f32) -> f32 {
+    let turns = (yaw / (2. * PI)).trunc();
+    if turns.abs() < 2. {
+        0.
+    } else {
+        turns * 2. * PI
+    }
+}
+
+/// Applies mouse motion and gamepad input to the first-person look targets
+pub fn first_person_look_input(
+    time: Res<Time>,
+    input: LookInputReader,
+    mut looks: Query<&mut FirstPersonLook>,
+) {
+    for mut look in &mut looks {
+        if !look.input_enabled {
+            continue;
+        }
+        let delta = input.look_delta(&look.input, time.delta());
+        if delta != Vec2::ZERO {
+            look.rotate(delta);
+        }
+    }
+}
+
+/// Ticks the look smoothing and writes yaw to the body and pitch to the head
+pub fn update_first_person_look(
+    time: Res<Time>,
+    mut bodies: Query<(&mut FirstPersonLook, &mut Transform)>,
+    mut heads: Query<&mut Transform, Without<FirstPersonLook>>,
+) {
+    for (mut look, mut body_transform) in &mut bodies {
+        look.tick(time.delta());
+        let angle = look.look();
+
+        body_transform.rotation = Quat::from_rotation_y(angle.y);
+        if let Some(mut head_transform) = look.head.and_then(|head| heads.get_mut(head).ok()) {
+            head_transform.rotation = Quat::from_rotation_x(angle.p);
+        }
+    }
+}
+
+/// Adds the first-person look systems. They run in `PostUpdate`, before transform
+/// propagation.
+pub struct FirstPersonLookPlugin;
+
+impl Plugin for FirstPersonLookPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (first_person_look_input, update_first_person_look)
+                .chain()
+                .before(TransformSystems::Propagate),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn pitch_is_clamped() {
+        let mut look = FirstPersonLook::default();
+        look.rotate(Vec2::new(0., 10.));
+
+        assert!(look.target.p < PI / 2.);
+    }
+
+    #[test]
+    fn linear_smoothing_lags_behind_target() {
+        let mut look = FirstPersonLook::default().with_linear_smoothing(1.);
+        look.rotate(Vec2::new(1., 0.));
+        look.tick(Duration::from_secs_f32(0.5));
+
+        assert!((look.look().y - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn rewrap_keeps_smoothing_offset() {
+        let mut look = FirstPersonLook::default().with_spring_smoothing(10., 1.);
+        look.rotate(Vec2::new(6. * PI, 0.));
+        look.tick(Duration::from_secs_f32(0.01));
+
+        let offset = look.target.y - look.look().y;
+        assert!(look.target.y.abs() < 2. * PI);
+        assert!(offset > 4. * PI, "Offset was {offset}");
+    }
+
+    #[test]
+    fn writes_yaw_to_body_and_pitch_to_head() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, update_first_person_look);
+
+        let head = app.world_mut().spawn(Transform::default()).id();
+        let mut look = FirstPersonLook::new(head);
+        look.rotate(Vec2::new(0.5, 0.3));
+        let body = app.world_mut().spawn(look).id();
+        app.update();
+
+        let body_rotation = app.world().get::<Transform>(body).unwrap().rotation;
+        let head_rotation = app.world().get::<Transform>(head).unwrap().rotation;
+        let forward = body_rotation * head_rotation * Vec3::NEG_Z;
+        let expected = PitchYawClamped::new(0.5, 0.3).to_unit_vec();
+
+        assert!(
+            forward.distance(expected) < EPSILON,
+            "Forward was {forward}"
+        );
+    }
+}
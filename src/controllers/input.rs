@@ -0,0 +1,169 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        gamepad::Gamepad,
+        mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Which gamepad stick drives the look direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum GamepadStick {
+    Left,
+    #[default]
+    Right,
+}
+
+/// Settings to turn mouse motion and gamepad stick input into look angle deltas.
+///
+/// Deltas are returned as a `Vec2` with yaw in `x` and pitch in `y`, the same layout
+/// used by the `PitchYaw` stepper derivatives.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct LookInput {
+    /// Radians per pixel of mouse motion
+    pub mouse_sensitivity: f32,
+    /// Radians per second at full stick deflection
+    pub gamepad_sensitivity: f32,
+    pub invert_x: bool,
+    pub invert_y: bool,
+    /// Stick deflection below which gamepad input is ignored
+    pub deadzone: f32,
+    pub stick: GamepadStick,
+}
+
+impl Default for LookInput {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 0.003,
+            gamepad_sensitivity: 3.,
+            invert_x: false,
+            invert_y: false,
+            deadzone: 0.15,
+            stick: GamepadStick::Right,
+        }
+    }
+}
+
+impl LookInput {
+    /// Yaw and pitch deltas for the given mouse motion (in pixels, y pointing down)
+    /// and stick deflection (y pointing up) over a frame of length `dt`
+    pub fn look_delta(&self, mouse_delta: Vec2, stick: Vec2, dt: Duration) -> Vec2 {
+        let stick = apply_deadzone(stick, self.deadzone);
+
+        let mouse = Vec2::new(-mouse_delta.x, -mouse_delta.y) * self.mouse_sensitivity;
+        let gamepad = Vec2::new(-stick.x, stick.y) * self.gamepad_sensitivity * dt.as_secs_f32();
+
+        let mut delta = mouse + gamepad;
+        if self.invert_x {
+            delta.x = -delta.x;
+        }
+        if self.invert_y {
+            delta.y = -delta.y;
+        }
+        delta
+    }
+}
+
+/// Radial deadzone: ignores deflections shorter than `deadzone` and rescales the rest
+/// so that the output still covers the full [0, 1] range
+pub fn apply_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    let length = stick.length();
+    if length <= deadzone || length == 0. {
+        Vec2::ZERO
+    } else {
+        let rescaled = ((length - deadzone) / (1. - deadzone)).min(1.);
+        stick * (rescaled / length)
+    }
+}
+
+/// Reads the raw look input for the current frame. Resources missing because the
+/// `InputPlugin` was not added read as no input.
+#[derive(SystemParam)]
+pub struct LookInputReader<'w, 's> {
+    mouse_motion: Option<Res<'w, AccumulatedMouseMotion>>,
+    mouse_scroll: Option<Res<'w, AccumulatedMouseScroll>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl LookInputReader<'_, '_> {
+    pub fn mouse_delta(&self) -> Vec2 {
+        self.mouse_motion
+            .as_ref()
+            .map(|motion| motion.delta)
+            .unwrap_or_default()
+    }
+
+    /// Vertical scroll this frame, in lines
+    pub fn scroll_lines(&self) -> f32 {
+        self.mouse_scroll
+            .as_ref()
+            .map(|scroll| match scroll.unit {
+                MouseScrollUnit::Line => scroll.delta.y,
+                MouseScrollUnit::Pixel => {
+                    scroll.delta.y / MouseScrollUnit::SCROLL_UNIT_CONVERSION_FACTOR
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sum of the given stick over all connected gamepads
+    pub fn stick(&self, stick: GamepadStick) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(|gamepad| match stick {
+                GamepadStick::Left => gamepad.left_stick(),
+                GamepadStick::Right => gamepad.right_stick(),
+            })
+            .sum()
+    }
+
+    /// Yaw and pitch deltas for this frame, see [`LookInput::look_delta`]
+    pub fn look_delta(&self, settings: &LookInput, dt: Duration) -> Vec2 {
+        settings.look_delta(self.mouse_delta(), self.stick(settings.stick), dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn deadzone_ignores_small_deflection() {
+        assert_eq!(apply_deadzone(Vec2::new(0.1, 0.05), 0.15), Vec2::ZERO);
+    }
+
+    #[test]
+    fn deadzone_rescales_to_full_range() {
+        let full = apply_deadzone(Vec2::new(0., 1.), 0.2);
+        let half = apply_deadzone(Vec2::new(0.6, 0.), 0.2);
+
+        assert!((full.length() - 1.).abs() < EPSILON);
+        assert!((half.length() - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn mouse_right_turns_right() {
+        let settings = LookInput::default();
+        let delta = settings.look_delta(Vec2::new(10., 0.), Vec2::ZERO, Duration::ZERO);
+
+        // Positive yaw turns towards -X, so turning right decreases it
+        assert!(delta.x < 0.);
+        assert_eq!(delta.y, 0.);
+    }
+
+    #[test]
+    fn invert_y_flips_pitch() {
+        let settings = LookInput {
+            invert_y: true,
+            ..default()
+        };
+        let delta = settings.look_delta(Vec2::ZERO, Vec2::new(0., 1.), Duration::from_secs(1));
+
+        assert!(delta.y < 0.);
+    }
+}
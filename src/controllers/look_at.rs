@@ -0,0 +1,243 @@
+use bevy::{prelude::*, transform::TransformSystems};
+use std::f32::consts::PI;
+
+use crate::prelude::{PitchYawClamped, SpringStepper, TickInterpolator, critical_damp_coeff};
+
+/// What a [`LookAtRig`] looks at
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LookAtTarget {
+    /// Return to the neutral pose
+    #[default]
+    None,
+    /// Look at the global translation of an entity
+    Entity(Entity),
+    /// Look at a point in world space
+    Position(Vec3),
+}
+
+/// A joint driven by a [`LookAtRig`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookAtJoint {
+    pub entity: Entity,
+    /// Fraction of the look rotation this joint takes. Weights along the chain usually
+    /// add up to 1.
+    pub weight: f32,
+    /// Maximum pitch this joint can contribute
+    pub clamp_p: f32,
+    /// Maximum yaw this joint can contribute
+    pub clamp_y: f32,
+    /// Local rotation of the joint in the neutral pose
+    pub rest_rotation: Quat,
+    /// Orientation of the joint relative to the rig root in the neutral pose. Identity
+    /// when the joint axes match the root's (Y up, -Z forward).
+    pub axes: Quat,
+}
+
+impl LookAtJoint {
+    pub fn new(entity: Entity, weight: f32, clamp_p: f32, clamp_y: f32) -> Self {
+        Self {
+            entity,
+            weight,
+            clamp_p,
+            clamp_y,
+            rest_rotation: Quat::IDENTITY,
+            axes: Quat::IDENTITY,
+        }
+    }
+
+    /// Local rotation for this joint when it contributes `angle` to the look direction
+    pub fn local_rotation(&self, angle: PitchYawClamped) -> Quat {
+        let look = Quat::from_rotation_y(angle.y) * Quat::from_rotation_x(angle.p);
+        self.rest_rotation * (self.axes.inverse() * look * self.axes)
+    }
+}
+
+/// Procedural look-at for a chain of joints (e.g. spine, neck, head, eyes), placed on
+/// the rig root.
+///
+/// The look direction is measured relative to the root's forward axis, smoothed with a
+/// spring, and distributed along `joints` in order. Each joint takes its weighted share,
+/// limited by its own clamps, and whatever it cannot take is passed down the chain.
+#[derive(Component, Debug, Clone)]
+pub struct LookAtRig {
+    pub joints: Vec<LookAtJoint>,
+    pub target: LookAtTarget,
+    /// Point the look direction is measured from, in the root's local space
+    pub origin: Vec3,
+    /// Smoothed look direction relative to the root. Its clamps are the allowed look
+    /// range: targets outside of it make the rig fall back to the neutral pose.
+    pub angle: SpringStepper<PitchYawClamped, Vec2>,
+}
+
+impl LookAtRig {
+    pub fn new(joints: Vec<LookAtJoint>, clamp_p: f32, clamp_y: f32) -> Self {
+        let spring = 60.;
+        Self {
+            joints,
+            target: LookAtTarget::None,
+            origin: Vec3::ZERO,
+            angle: SpringStepper::new(
+                PitchYawClamped::new_with_clamps(0., 0., clamp_p, clamp_y),
+                spring,
+                critical_damp_coeff(spring),
+            ),
+        }
+    }
+
+    /// Look direction relative to the root for a world-space `target` point, or the
+    /// neutral direction if it is out of the allowed range
+    pub fn desired_angle(&self, root: &GlobalTransform, target: Vec3) -> PitchYawClamped {
+        let range = self.angle.current;
+        let local = root.affine().inverse().transform_point3(target) - self.origin;
+        let neutral = PitchYawClamped::new_with_clamps(0., 0., range.clamp_p, range.clamp_y);
+
+        if local.length_squared() < 1e-8 {
+            return neutral;
+        }
+        let PitchYawClamped { y, p, .. } = PitchYawClamped::from_vec(local);
+        if p.abs() > range.clamp_p || y.abs() > range.clamp_y {
+            neutral
+        } else {
+            PitchYawClamped::new_with_clamps(y, p, range.clamp_p, range.clamp_y)
+        }
+    }
+
+    /// Splits `angle` between the joints, see [`distribute_look_angle`]
+    pub fn joint_angles(&self, angle: PitchYawClamped) -> Vec<PitchYawClamped> {
+        distribute_look_angle(angle, &self.joints)
+    }
+}
+
+impl Default for LookAtRig {
+    fn default() -> Self {
+        Self::new(Vec::new(), PI / 3., PI / 2.)
+    }
+}
+
+/// Splits `angle` along a chain of joints. Each joint takes `weight` times the total,
+/// plus whatever previous joints could not take because of their clamps.
+pub fn distribute_look_angle(
+    angle: PitchYawClamped,
+    joints: &[LookAtJoint],
+) -> Vec<PitchYawClamped> {
+    let mut carry = Vec2::ZERO;
+    joints
+        .iter()
+        .map(|joint| {
+            let wanted = Vec2::new(angle.y, angle.p) * joint.weight + carry;
+            let share =
+                PitchYawClamped::new_with_clamps(wanted.x, wanted.y, joint.clamp_p, joint.clamp_y)
+                    .normalize();
+            carry = wanted - Vec2::new(share.y, share.p);
+            share
+        })
+        .collect()
+}
+
+/// Updates the look direction of every [`LookAtRig`] and writes the local rotations of
+/// their joints
+pub fn update_look_at_rigs(
+    time: Res<Time>,
+    targets: Query<&GlobalTransform>,
+    mut rigs: Query<(&mut LookAtRig, &GlobalTransform)>,
+    mut joints: Query<&mut Transform, Without<LookAtRig>>,
+) {
+    for (mut rig, root) in &mut rigs {
+        let target = match rig.target {
+            LookAtTarget::None => None,
+            LookAtTarget::Entity(entity) => targets.get(entity).ok().map(|t| t.translation()),
+            LookAtTarget::Position(position) => Some(position),
+        };
+        let desired = match target {
+            Some(target) => rig.desired_angle(root, target),
+            None => PitchYawClamped::new_with_clamps(
+                0.,
+                0.,
+                rig.angle.current.clamp_p,
+                rig.angle.current.clamp_y,
+            ),
+        };
+        rig.angle.set_target(desired);
+        rig.angle.tick(time.delta());
+
+        let angles = rig.joint_angles(rig.angle.get());
+        for (joint, angle) in rig.joints.iter().zip(angles) {
+            if let Ok(mut transform) = joints.get_mut(joint.entity) {
+                transform.rotation = joint.local_rotation(angle);
+            }
+        }
+    }
+}
+
+/// Adds the look-at rig system. It runs in `PostUpdate`, before transform propagation,
+/// so it should be ordered after any animation system that writes the same joints.
+pub struct LookAtPlugin;
+
+impl Plugin for LookAtPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_look_at_rigs.before(TransformSystems::Propagate),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    fn chain() -> Vec<LookAtJoint> {
+        vec![
+            LookAtJoint::new(Entity::PLACEHOLDER, 0.2, 0.1, 0.2),
+            LookAtJoint::new(Entity::PLACEHOLDER, 0.3, 0.3, 0.5),
+            LookAtJoint::new(Entity::PLACEHOLDER, 0.5, 0.5, 1.),
+        ]
+    }
+
+    #[test]
+    fn shares_add_up_to_total() {
+        let angle = PitchYawClamped::new(0.8, 0.4);
+        let shares = distribute_look_angle(angle, &chain());
+
+        let total_y: f32 = shares.iter().map(|share| share.y).sum();
+        let total_p: f32 = shares.iter().map(|share| share.p).sum();
+
+        assert!((total_y - 0.8).abs() < EPSILON, "Total yaw was {total_y}");
+        assert!((total_p - 0.4).abs() < EPSILON, "Total pitch was {total_p}");
+    }
+
+    #[test]
+    fn clamped_share_is_carried_down_the_chain() {
+        let angle = PitchYawClamped::new(1.5, 0.);
+        let shares = distribute_look_angle(angle, &chain());
+
+        assert!((shares[0].y - 0.2).abs() < EPSILON);
+        // 0.3 * 1.5 plus the 0.1 the first joint could not take
+        assert!((shares[1].y - 0.5).abs() < EPSILON);
+        assert!((shares[2].y - 0.8).abs() < EPSILON);
+    }
+
+    #[test]
+    fn out_of_range_target_falls_back_to_neutral() {
+        let rig = LookAtRig::new(chain(), 0.5, 1.);
+        let root = GlobalTransform::IDENTITY;
+
+        let in_range = rig.desired_angle(&root, Vec3::new(-1., 0., -2.));
+        let behind = rig.desired_angle(&root, Vec3::new(0., 0., 5.));
+
+        assert!(in_range.y > 0.);
+        assert_eq!((behind.y, behind.p), (0., 0.));
+    }
+
+    #[test]
+    fn joint_rotation_looks_along_share() {
+        let joint = LookAtJoint::new(Entity::PLACEHOLDER, 1., 1., 1.);
+        let angle = PitchYawClamped::new(0.4, -0.3);
+
+        let forward = joint.local_rotation(angle) * Vec3::NEG_Z;
+
+        assert!(forward.distance(angle.to_unit_vec()) < EPSILON);
+    }
+}
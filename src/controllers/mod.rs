@@ -0,0 +1,5 @@
+pub mod first_person;
+pub mod input;
+pub mod look_at;
+pub mod orbit_camera;
+pub mod turret;
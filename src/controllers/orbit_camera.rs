@@ -0,0 +1,279 @@
+use bevy::{prelude::*, transform::TransformSystems};
+use std::{f32::consts::PI, fmt, sync::Arc};
+
+use super::{
+    first_person::yaw_rewrap_shift,
+    input::{LookInput, LookInputReader},
+};
+use crate::prelude::{PitchYawClamped, SpringStepper, TickInterpolator, critical_damp_coeff};
+
+/// Callback used to pull the camera in front of obstacles.
+///
+/// It is called with the orbit center, the direction from the center towards the camera
+/// and the desired distance, and returns the distance at which the first obstacle was
+/// found, or `None` if the way is clear. This maps directly onto a raycast in most
+/// physics engines.
+#[derive(Clone)]
+pub struct OrbitCollision(pub Arc<dyn Fn(Vec3, Dir3, f32) -> Option<f32> + Send + Sync>);
+
+impl OrbitCollision {
+    pub fn new(callback: impl Fn(Vec3, Dir3, f32) -> Option<f32> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for OrbitCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OrbitCollision").finish_non_exhaustive()
+    }
+}
+
+/// Third-person camera orbiting around an entity.
+///
+/// The look direction, distance and orbit center are each smoothed by a
+/// [`SpringStepper`]. The camera entity is expected to have no parent, as the result
+/// is written directly into its [`Transform`].
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct OrbitCamera {
+    /// Entity whose global translation the camera orbits around
+    pub center: Entity,
+    /// Offset added to the center entity's translation, in world space
+    pub center_offset: Vec3,
+    /// Direction the camera looks in. Yaw is not clamped by default, so that the
+    /// camera can keep orbiting without crossing the yaw wrapping boundary.
+    pub angle: SpringStepper<PitchYawClamped, Vec2>,
+    pub distance: SpringStepper<f32>,
+    pub center_position: SpringStepper<Vec3>,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// Distance change per scrolled line
+    pub zoom_speed: f32,
+    pub input: LookInput,
+    /// Whether to read mouse and gamepad input. Disable to drive `angle` and
+    /// `distance` targets manually.
+    pub input_enabled: bool,
+    /// Distance kept between the camera and the obstacles reported by `collision`
+    pub collision_margin: f32,
+    pub collision: Option<OrbitCollision>,
+}
+
+impl OrbitCamera {
+    pub fn new(center: Entity) -> Self {
+        let spring = 120.;
+        let damping = critical_damp_coeff(spring);
+        let angle = PitchYawClamped::new_with_clamps(0., -0.3, PI / 2. - 0.01, f32::MAX);
+
+        Self {
+            center,
+            center_offset: Vec3::ZERO,
+            angle: SpringStepper::new(angle, spring, damping),
+            distance: SpringStepper::new(5., spring, damping),
+            center_position: SpringStepper::new(Vec3::ZERO, spring, damping),
+            min_distance: 1.,
+            max_distance: 20.,
+            zoom_speed: 0.5,
+            input: LookInput::default(),
+            input_enabled: true,
+            collision_margin: 0.2,
+            collision: None,
+        }
+    }
+
+    pub fn with_collision(
+        mut self,
+        callback: impl Fn(Vec3, Dir3, f32) -> Option<f32> + Send + Sync + 'static,
+    ) -> Self {
+        self.collision = Some(OrbitCollision::new(callback));
+        self
+    }
+
+    /// Rotates the target look direction by `delta` (yaw in `x`, pitch in `y`)
+    pub fn rotate(&mut self, delta: Vec2) {
+        let target = self.angle.target + PitchYawClamped::new(delta.x, delta.y);
+        self.angle.set_target(target.normalize());
+    }
+
+    /// Changes the target distance by `delta`, within the zoom limits
+    pub fn zoom(&mut self, delta: f32) {
+        let target = (self.distance.target + delta).clamp(self.min_distance, self.max_distance);
+        self.distance.set_target(target);
+    }
+
+    /// Jumps all steppers to their targets, e.g. after a teleport
+    pub fn snap(&mut self) {
+        self.angle.current = self.angle.target;
+        self.angle.velocity = Vec2::ZERO;
+        self.distance.current = self.distance.target;
+        self.distance.velocity = 0.;
+        self.center_position.current = self.center_position.target;
+        self.center_position.velocity = Vec3::ZERO;
+    }
+
+    /// Keeps the unclamped yaw from growing without bound, shifting the target and the
+    /// smoothed value together so that the spring is unaffected
+    fn rewrap_yaw(&mut self) {
+        let shift = yaw_rewrap_shift(self.angle.target.y);
+        self.angle.target.y -= shift;
+        self.angle.current.y -= shift;
+    }
+
+    /// Camera transform for the current (smoothed) values, ignoring collisions
+    pub fn camera_transform(&self) -> Transform {
+        camera_transform(
+            self.center_position.get(),
+            self.angle.get().to_unit_vec(),
+            self.distance.get(),
+        )
+    }
+}
+
+fn camera_transform(center: Vec3, look_dir: Vec3, distance: f32) -> Transform {
+    Transform::from_translation(center - look_dir * distance).looking_to(look_dir, Vec3::Y)
+}
+
+/// Applies mouse motion, scrolling and gamepad input to the orbit camera targets
+pub fn orbit_camera_input(
+    time: Res<Time>,
+    input: LookInputReader,
+    mut cameras: Query<&mut OrbitCamera>,
+) {
+    for mut camera in &mut cameras {
+        if !camera.input_enabled {
+            continue;
+        }
+        let delta = input.look_delta(&camera.input, time.delta());
+        let zoom = -input.scroll_lines() * camera.zoom_speed;
+
+        if delta != Vec2::ZERO {
+            camera.rotate(delta);
+        }
+        if zoom != 0. {
+            camera.zoom(zoom);
+        }
+    }
+}
+
+/// Ticks the orbit camera steppers and writes the result into the camera transform
+pub fn update_orbit_cameras(
+    time: Res<Time>,
+    centers: Query<&GlobalTransform>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+) {
+    for (mut camera, mut transform) in &mut cameras {
+        if let Ok(center) = centers.get(camera.center) {
+            let target = center.translation() + camera.center_offset;
+            camera.center_position.set_target(target);
+        }
+        let min_distance = camera.min_distance;
+        let max_distance = camera.max_distance;
+        camera.distance.target = camera.distance.target.clamp(min_distance, max_distance);
+
+        if camera.is_added() {
+            camera.snap();
+        } else {
+            camera.angle.tick(time.delta());
+            camera.distance.tick(time.delta());
+            camera.center_position.tick(time.delta());
+        }
+        camera.rewrap_yaw();
+
+        let center = camera.center_position.get();
+        let look_dir = camera.angle.get().to_unit_vec();
+
+        if let (Some(collision), Ok(back)) = (&camera.collision, Dir3::new(-look_dir)) {
+            let desired = camera.distance.get();
+            if let Some(hit) = (collision.0)(center, back, desired) {
+                let allowed = (hit - camera.collision_margin).max(0.);
+                if allowed < desired {
+                    // Pull in instantly, then let the spring ease back out once clear
+                    camera.distance.current = allowed;
+                    camera.distance.velocity = 0.;
+                }
+            }
+        }
+
+        *transform = camera_transform(center, look_dir, camera.distance.get());
+    }
+}
+
+/// Adds the orbit camera systems. They run in `PostUpdate`, before transform
+/// propagation, and use the orbit center's global transform from the previous frame.
+pub struct OrbitCameraPlugin;
+
+impl Plugin for OrbitCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (orbit_camera_input, update_orbit_cameras)
+                .chain()
+                .before(TransformSystems::Propagate),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn camera_looks_at_center() {
+        let mut camera = OrbitCamera::new(Entity::PLACEHOLDER);
+        camera.center_position.current = Vec3::new(1., 2., 3.);
+        camera.angle.current = PitchYawClamped::new(0.8, -0.4);
+        camera.distance.current = 4.;
+
+        let transform = camera.camera_transform();
+        let to_center = Vec3::new(1., 2., 3.) - transform.translation;
+
+        assert!((to_center.length() - 4.).abs() < EPSILON);
+        assert!(transform.forward().dot(to_center.normalize()) > 1. - EPSILON);
+    }
+
+    #[test]
+    fn zoom_respects_limits() {
+        let mut camera = OrbitCamera::new(Entity::PLACEHOLDER);
+        camera.zoom(100.);
+        assert_eq!(camera.distance.target, camera.max_distance);
+
+        camera.zoom(-100.);
+        assert_eq!(camera.distance.target, camera.min_distance);
+    }
+
+    #[test]
+    fn rewrap_keeps_spring_offset() {
+        let mut camera = OrbitCamera::new(Entity::PLACEHOLDER);
+        camera.rotate(Vec2::new(6. * PI, 0.));
+        let before = camera.camera_transform();
+        camera.rewrap_yaw();
+
+        let offset = camera.angle.target.y - camera.angle.current.y;
+        assert!(camera.angle.target.y.abs() < 2. * PI);
+        assert!((offset - 6. * PI).abs() < EPSILON, "Offset was {offset}");
+        let after = camera.camera_transform();
+        assert!(before.translation.distance(after.translation) < EPSILON);
+    }
+
+    #[test]
+    fn collision_pulls_camera_in() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, update_orbit_cameras);
+
+        let center = app
+            .world_mut()
+            .spawn(GlobalTransform::from_translation(Vec3::new(0., 1., 0.)))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn(OrbitCamera::new(center).with_collision(|_, _, _| Some(2.)))
+            .id();
+        app.update();
+
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        let distance = transform.translation.distance(Vec3::new(0., 1., 0.));
+        assert!((distance - 1.8).abs() < EPSILON, "Distance was {distance}");
+    }
+}
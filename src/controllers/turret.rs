@@ -0,0 +1,227 @@
+use bevy::{prelude::*, transform::TransformSystems};
+use std::f32::consts::PI;
+
+use crate::prelude::{LinearStepper, PitchYawClamped, TickInterpolator};
+
+/// Turret aiming, placed on the turret mount.
+///
+/// Angles are relative to the mount's [`GlobalTransform`]. Yaw is written to the `base`
+/// entity and pitch to the `barrel` entity, which is expected to be a child of the base.
+/// The mount-relative limits are the clamps of the `aim` stepper. An infinite yaw clamp
+/// lets the base turn all the way around, always taking the short way;
+/// [`Turret::with_limits`] uses one for any yaw limit of `PI` or more.
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct Turret {
+    pub base: Entity,
+    pub barrel: Entity,
+    /// World-space point to aim at, if any
+    pub target: Option<Vec3>,
+    /// Pivot point of the barrel, in the mount's local space
+    pub pivot: Vec3,
+    /// Current aim, turning at separate yaw (`x`) and pitch (`y`) speeds, in radians
+    /// per second
+    pub aim: LinearStepper<PitchYawClamped, Vec2>,
+    /// Angle between the barrel and the target below which the turret can fire
+    pub fire_tolerance: f32,
+    on_target: bool,
+}
+
+impl Turret {
+    pub fn new(base: Entity, barrel: Entity, yaw_speed: f32, pitch_speed: f32) -> Self {
+        Self {
+            base,
+            barrel,
+            target: None,
+            pivot: Vec3::ZERO,
+            aim: LinearStepper::new_with_speed(
+                PitchYawClamped::new_with_clamps(0., 0., PI / 2. - 0.001, f32::INFINITY),
+                Vec2::new(yaw_speed, pitch_speed),
+            ),
+            fire_tolerance: 0.02,
+            on_target: false,
+        }
+    }
+
+    /// Sets the mount-relative limits. A yaw limit of `PI` or more covers every
+    /// direction, so it leaves the yaw unlimited.
+    pub fn with_limits(mut self, clamp_p: f32, clamp_y: f32) -> Self {
+        let clamp_y = if clamp_y >= PI {
+            f32::INFINITY
+        } else {
+            clamp_y
+        };
+        let PitchYawClamped { y, p, .. } = self.aim.current;
+        let aim = PitchYawClamped::new_with_clamps(y, p, clamp_p, clamp_y).normalize();
+        self.aim.current = aim;
+        self.aim.target = aim;
+        self
+    }
+
+    /// Whether the barrel was within `fire_tolerance` of the target on the last update
+    pub fn can_fire(&self) -> bool {
+        self.on_target
+    }
+
+    /// Mount-relative aim angles that point the barrel at the world-space `target`
+    pub fn desired_aim(&self, mount: &GlobalTransform, target: Vec3) -> PitchYawClamped {
+        let current = self.aim.current;
+        let local = mount.affine().inverse().transform_point3(target) - self.pivot;
+        let PitchYawClamped { mut y, p, .. } = PitchYawClamped::from_vec(local);
+
+        if current.clamp_y.is_infinite() {
+            // Unlimited yaw: pick the equivalent yaw closest to the current one
+            y = current.y + ((y - current.y + PI).rem_euclid(2. * PI) - PI);
+        }
+
+        PitchYawClamped::new_with_clamps(y, p, current.clamp_p, current.clamp_y).normalize()
+    }
+
+    /// Keeps unlimited yaw from growing without bound, shifting the current and target
+    /// aim together
+    fn rewrap_yaw(&mut self) {
+        if self.aim.current.clamp_y.is_finite() {
+            return;
+        }
+        let shift = (self.aim.current.y / (2. * PI)).trunc() * 2. * PI;
+        self.aim.current.y -= shift;
+        self.aim.target.y -= shift;
+    }
+}
+
+/// Turns every [`Turret`] towards its target and updates whether it can fire
+pub fn update_turrets(
+    time: Res<Time>,
+    mut turrets: Query<(&mut Turret, &GlobalTransform)>,
+    mut parts: Query<&mut Transform, Without<Turret>>,
+) {
+    for (mut turret, mount) in &mut turrets {
+        let Some(target) = turret.target else {
+            turret.on_target = false;
+            continue;
+        };
+        turret.rewrap_yaw();
+        let desired = turret.desired_aim(mount, target);
+        turret.aim.set_target(desired);
+        turret.aim.tick(time.delta());
+
+        let aim = turret.aim.get();
+        let local = mount.affine().inverse().transform_point3(target) - turret.pivot;
+        turret.on_target =
+            local.length_squared() > 0. && aim.angle_between(local) <= turret.fire_tolerance;
+
+        if let Ok(mut base) = parts.get_mut(turret.base) {
+            base.rotation = Quat::from_rotation_y(aim.y);
+        }
+        if let Ok(mut barrel) = parts.get_mut(turret.barrel) {
+            barrel.rotation = Quat::from_rotation_x(aim.p);
+        }
+    }
+}
+
+/// Adds the turret system. It runs in `PostUpdate`, before transform propagation, and
+/// uses the mount's global transform from the previous frame.
+pub struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_turrets.before(TransformSystems::Propagate),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn yaw_and_pitch_turn_at_their_own_speed() {
+        let mut turret = Turret::new(Entity::PLACEHOLDER, Entity::PLACEHOLDER, 1., 0.5);
+        turret.aim.set_target(PitchYawClamped::new(2., 1.));
+        turret.aim.tick(Duration::from_secs(1));
+
+        let aim = turret.aim.get();
+        assert!((aim.y - 1.).abs() < EPSILON);
+        assert!((aim.p - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn unlimited_yaw_takes_short_way() {
+        let mut turret = Turret::new(Entity::PLACEHOLDER, Entity::PLACEHOLDER, 1., 1.);
+        turret.aim.current.y = PI - 0.1;
+
+        let target = PitchYawClamped::new(-PI + 0.1, 0.).to_unit_vec() * 10.;
+        let desired = turret.desired_aim(&GlobalTransform::IDENTITY, target);
+
+        assert!(
+            (desired.y - (PI + 0.1)).abs() < EPSILON,
+            "Desired {desired:?}"
+        );
+    }
+
+    #[test]
+    fn limited_yaw_is_clamped() {
+        let turret =
+            Turret::new(Entity::PLACEHOLDER, Entity::PLACEHOLDER, 1., 1.).with_limits(0.5, 1.);
+
+        let target = PitchYawClamped::new(2., 1.).to_unit_vec();
+        let desired = turret.desired_aim(&GlobalTransform::IDENTITY, target);
+
+        assert_eq!((desired.y, desired.p), (1., 0.5));
+    }
+
+    #[test]
+    fn can_fire_once_on_target() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, update_turrets);
+
+        let base = app.world_mut().spawn(Transform::default()).id();
+        let barrel = app.world_mut().spawn(Transform::default()).id();
+        let mut turret = Turret::new(base, barrel, 1., 1.);
+        turret.target = Some(Vec3::new(0., 0., -10.));
+        let mount = app
+            .world_mut()
+            .spawn((turret, GlobalTransform::IDENTITY))
+            .id();
+        app.update();
+        assert!(app.world().get::<Turret>(mount).unwrap().can_fire());
+
+        app.world_mut().get_mut::<Turret>(mount).unwrap().target = Some(Vec3::new(10., 0., 0.));
+        app.update();
+        assert!(!app.world().get::<Turret>(mount).unwrap().can_fire());
+    }
+
+    #[test]
+    fn yaw_limit_of_pi_crosses_back_of_mount() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, update_turrets);
+
+        let base = app.world_mut().spawn(Transform::default()).id();
+        let barrel = app.world_mut().spawn(Transform::default()).id();
+        let mut turret = Turret::new(base, barrel, 1., 1.).with_limits(1., PI);
+        turret.aim.current.y = PI - 0.2;
+        turret.target = Some(PitchYawClamped::new(-PI + 0.2, 0.).to_unit_vec() * 10.);
+        let mount = app
+            .world_mut()
+            .spawn((turret, GlobalTransform::IDENTITY))
+            .id();
+
+        for _ in 0..10 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(50));
+            app.update();
+        }
+
+        let turret = app.world().get::<Turret>(mount).unwrap();
+        // Going the long way round would take more than 5 seconds
+        assert!(turret.can_fire(), "Aim {:?}", turret.aim.get());
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul, Sub},
+};
+
+/// Planar rotation angle in radians. Wraps around [-PI, PI], with 0 pointing along +X
+/// and positive angles turning counterclockwise, matching [`Rot2`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Angle(pub f32);
+
+impl Angle {
+    pub fn new(radians: f32) -> Self {
+        Self(radians)
+    }
+
+    pub fn degrees(degrees: f32) -> Self {
+        Self(degrees.to_radians())
+    }
+
+    pub fn as_radians(&self) -> f32 {
+        self.0
+    }
+
+    pub fn as_degrees(&self) -> f32 {
+        self.0.to_degrees()
+    }
+
+    pub fn to_unit_vec(&self) -> Vec2 {
+        Vec2::from_angle(self.0)
+    }
+
+    pub fn from_vec(dir: Vec2) -> Self {
+        Self(dir.y.atan2(dir.x))
+    }
+
+    pub fn to_rot2(&self) -> Rot2 {
+        Rot2::radians(self.0)
+    }
+
+    pub fn from_rot2(rot: Rot2) -> Self {
+        Self(rot.as_radians())
+    }
+
+    pub fn length(&self) -> f32 {
+        self.0.abs()
+    }
+
+    pub fn distance(&self, other: &Self) -> f32 {
+        (*self - *other).length()
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self((self.0 + PI).rem_euclid(2. * PI) - PI)
+    }
+
+    /// Takes into account the wrapping, in the same way as `PitchYaw::sub_pitchyaw` does
+    /// for yaw
+    pub fn sub_angle(self, other: Self) -> Self {
+        Self((self.0 - other.0 + PI).rem_euclid(2. * PI) - PI)
+    }
+
+    pub fn step_toward(&self, target: Angle, dangle: f32) -> Self {
+        let delta = target.sub_angle(*self);
+
+        if delta.0.abs() < dangle {
+            target.normalize()
+        } else {
+            Self(self.0 + dangle * delta.0.signum()).normalize()
+        }
+    }
+}
+
+impl From<Vec2> for Angle {
+    fn from(dir: Vec2) -> Self {
+        Self::from_vec(dir)
+    }
+}
+
+impl From<Rot2> for Angle {
+    fn from(rot: Rot2) -> Self {
+        Self::from_rot2(rot)
+    }
+}
+
+impl From<Angle> for Rot2 {
+    fn from(angle: Angle) -> Self {
+        angle.to_rot2()
+    }
+}
+
+impl Sub<Angle> for Angle {
+    type Output = Angle;
+
+    fn sub(self, rhs: Angle) -> Self::Output {
+        self.sub_angle(rhs)
+    }
+}
+
+impl Add<Angle> for Angle {
+    type Output = Angle;
+
+    fn add(self, rhs: Angle) -> Self::Output {
+        Angle(self.0 + rhs.0)
+    }
+}
+
+impl Mul<f32> for Angle {
+    type Output = Angle;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Angle(self.0 * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{LinearStepper, SpringStepper, TickInterpolator, critical_damp_coeff};
+    use std::time::Duration;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn wrapped_sub_takes_short_way() {
+        let a = Angle(PI - 0.1);
+        let b = Angle(-PI + 0.1);
+
+        assert!(((b - a).0 - 0.2).abs() < EPSILON);
+        assert!(((a - b).0 + 0.2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn step_toward_near_wrap() {
+        let stepped = Angle(PI - 0.1).step_toward(Angle(-PI + 0.1), 0.15);
+
+        assert!(
+            stepped.distance(&Angle(-PI + 0.05)) < EPSILON,
+            "{stepped:?}"
+        );
+    }
+
+    #[test]
+    fn matches_rot2() {
+        let angle = Angle::degrees(135.);
+        let rot = angle.to_rot2();
+
+        assert!((rot * Vec2::X).distance(angle.to_unit_vec()) < EPSILON);
+        assert!(Angle::from(rot).distance(&angle) < EPSILON);
+        assert!(Angle::from_vec(angle.to_unit_vec()).distance(&angle) < EPSILON);
+    }
+
+    #[test]
+    fn steppers_turn_the_short_way() {
+        let from = Angle(PI - 0.1);
+        let to = Angle(-PI + 0.1);
+
+        let mut linear = LinearStepper::new(from.to_rot2(), 0.1);
+        linear.set_target(to.to_rot2());
+        linear.tick(Duration::from_secs(1));
+        let stepped = Angle::from(linear.get());
+        assert!(stepped.distance(&Angle(PI)) < EPSILON, "{stepped:?}");
+
+        let mut spring = SpringStepper::new(from, 50., critical_damp_coeff(50.));
+        spring.set_target(to);
+        for _ in 0..200 {
+            spring.tick(Duration::from_millis(10));
+            // Never goes the long way around through 0
+            assert!(spring.get().0.abs() > PI - 0.2, "{:?}", spring.get());
+        }
+        assert!(spring.get().distance(&to) < 0.001);
+    }
+}
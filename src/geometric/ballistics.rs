@@ -0,0 +1,286 @@
+use bevy::math::{prelude::*, primitives::InfinitePlane3d};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::pitchyaw::PitchYaw;
+
+/// Launch direction hitting a target, and how long the projectile takes to get there
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BallisticSolution {
+    pub aim: PitchYaw,
+    pub flight_time: f32,
+}
+
+/// Launch directions hitting a target at a given muzzle speed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BallisticSolutions {
+    /// The target is out of range
+    None,
+    /// The target is exactly at maximum range, both arcs coincide
+    One(BallisticSolution),
+    Two {
+        low: BallisticSolution,
+        high: BallisticSolution,
+    },
+}
+
+impl BallisticSolutions {
+    /// The flattest arc, which is also the fastest to arrive
+    pub fn low(&self) -> Option<BallisticSolution> {
+        match *self {
+            Self::None => None,
+            Self::One(solution) => Some(solution),
+            Self::Two { low, .. } => Some(low),
+        }
+    }
+
+    /// The highest arc
+    pub fn high(&self) -> Option<BallisticSolution> {
+        match *self {
+            Self::None => None,
+            Self::One(solution) => Some(solution),
+            Self::Two { high, .. } => Some(high),
+        }
+    }
+}
+
+/// Finds the launch directions that make a projectile fired from `origin` at `speed`
+/// hit `target`, under a gravity of magnitude `gravity` pointing down the Y axis.
+pub fn solve_launch(origin: Vec3, target: Vec3, speed: f32, gravity: f32) -> BallisticSolutions {
+    let delta = target - origin;
+    let horizontal = Vec2::new(delta.x, delta.z);
+    let x = horizontal.length();
+    let y = delta.y;
+    let v2 = speed * speed;
+
+    if gravity <= 0. {
+        // No drop, the only solution is a straight line
+        return if speed > 0. && delta.length_squared() > 0. {
+            BallisticSolutions::One(BallisticSolution {
+                aim: PitchYaw::from_vec(delta),
+                flight_time: delta.length() / speed,
+            })
+        } else {
+            BallisticSolutions::None
+        };
+    }
+
+    let discriminant = v2 * v2 - gravity * (gravity * x * x + 2. * y * v2);
+    if discriminant < 0. || speed <= 0. {
+        return BallisticSolutions::None;
+    }
+
+    if x < 1e-6 {
+        return vertical_solutions(y, speed, gravity);
+    }
+
+    let yaw = PitchYaw::from_vec(Vec3::new(delta.x, 0., delta.z)).y;
+    let solution = |tan_pitch: f32| {
+        let pitch = tan_pitch.atan();
+        BallisticSolution {
+            aim: PitchYaw::new(yaw, pitch),
+            flight_time: x / (speed * pitch.cos()),
+        }
+    };
+
+    let root = discriminant.sqrt();
+    let low = solution((v2 - root) / (gravity * x));
+    if root == 0. {
+        BallisticSolutions::One(low)
+    } else {
+        let high = solution((v2 + root) / (gravity * x));
+        BallisticSolutions::Two { low, high }
+    }
+}
+
+/// Solutions for a target straight above or below the origin
+fn vertical_solutions(y: f32, speed: f32, gravity: f32) -> BallisticSolutions {
+    // Roots of y = speed * t - gravity * t^2 / 2 (firing up) and of
+    // y = -speed * t - gravity * t^2 / 2 (firing down)
+    let root = (speed * speed - 2. * gravity * y).sqrt();
+    let up = |flight_time: f32| BallisticSolution {
+        aim: PitchYaw::new(0., PI / 2.),
+        flight_time,
+    };
+
+    if y >= 0. {
+        BallisticSolutions::One(up((speed - root) / gravity))
+    } else {
+        BallisticSolutions::Two {
+            low: BallisticSolution {
+                aim: PitchYaw::new(0., -PI / 2.),
+                flight_time: (root - speed) / gravity,
+            },
+            high: up((speed + root) / gravity),
+        }
+    }
+}
+
+/// Point where a [`Trajectory`] crosses a plane
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Impact {
+    pub point: Vec3,
+    pub time: f32,
+}
+
+/// Path of a projectile under constant gravity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Trajectory {
+    pub origin: Vec3,
+    pub velocity: Vec3,
+    pub gravity: Vec3,
+}
+
+impl Trajectory {
+    pub fn new(origin: Vec3, velocity: Vec3, gravity: Vec3) -> Self {
+        Self {
+            origin,
+            velocity,
+            gravity,
+        }
+    }
+
+    /// Trajectory of a projectile fired from `origin` towards `aim` at `speed`, under a
+    /// gravity of magnitude `gravity` pointing down the Y axis
+    pub fn from_aim(origin: Vec3, aim: PitchYaw, speed: f32, gravity: f32) -> Self {
+        Self::new(origin, aim.to_unit_vec() * speed, Vec3::NEG_Y * gravity)
+    }
+
+    pub fn position_at(&self, time: f32) -> Vec3 {
+        self.origin + self.velocity * time + self.gravity * (0.5 * time * time)
+    }
+
+    pub fn velocity_at(&self, time: f32) -> Vec3 {
+        self.velocity + self.gravity * time
+    }
+
+    /// First point after launch where the trajectory crosses the plane going through
+    /// `plane_origin` with the given normal
+    pub fn impact_on_plane(&self, plane_origin: Vec3, plane: InfinitePlane3d) -> Option<Impact> {
+        let normal = *plane.normal;
+        // n . (origin + v t + g t^2 / 2 - plane_origin) = 0
+        let a = 0.5 * normal.dot(self.gravity);
+        let b = normal.dot(self.velocity);
+        let c = normal.dot(self.origin - plane_origin);
+
+        let time = if a.abs() < 1e-9 {
+            if b.abs() < 1e-9 {
+                return None;
+            }
+            Some(-c / b).filter(|t| *t > 1e-6)
+        } else {
+            let discriminant = b * b - 4. * a * c;
+            if discriminant < 0. {
+                return None;
+            }
+            let root = discriminant.sqrt();
+            let t0 = (-b - root) / (2. * a);
+            let t1 = (-b + root) / (2. * a);
+            [t0.min(t1), t0.max(t1)].into_iter().find(|t| *t > 1e-6)
+        }?;
+
+        Some(Impact {
+            point: self.position_at(time),
+            time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{LinearStepper, TickInterpolator};
+    use std::time::Duration;
+
+    const EPSILON: f32 = 0.001;
+    const GRAVITY: f32 = 9.81;
+
+    fn assert_hits(origin: Vec3, target: Vec3, speed: f32, solution: BallisticSolution) {
+        let trajectory = Trajectory::from_aim(origin, solution.aim, speed, GRAVITY);
+        let landed = trajectory.position_at(solution.flight_time);
+        let miss = landed.distance(target);
+
+        assert!(miss < EPSILON, "Solution {solution:?} missed by {miss}");
+    }
+
+    #[test]
+    fn both_arcs_hit_target() {
+        let origin = Vec3::new(1., 2., 3.);
+        let target = Vec3::new(-20., 5., 14.);
+        let speed = 25.;
+
+        let BallisticSolutions::Two { low, high } = solve_launch(origin, target, speed, GRAVITY)
+        else {
+            panic!("Expected two solutions");
+        };
+
+        assert!(low.aim.p < high.aim.p);
+        assert!(low.flight_time < high.flight_time);
+        assert_hits(origin, target, speed, low);
+        assert_hits(origin, target, speed, high);
+    }
+
+    #[test]
+    fn out_of_range_has_no_solution() {
+        let solutions = solve_launch(Vec3::ZERO, Vec3::new(1000., 0., 0.), 10., GRAVITY);
+
+        assert_eq!(solutions, BallisticSolutions::None);
+    }
+
+    #[test]
+    fn max_range_has_one_solution_at_45_degrees() {
+        let speed = 10.;
+        let max_range = speed * speed / GRAVITY;
+
+        let solution = solve_launch(Vec3::ZERO, Vec3::new(0., 0., -max_range), speed, GRAVITY)
+            .low()
+            .unwrap();
+
+        assert!((solution.aim.p - PI / 4.).abs() < EPSILON);
+        assert!(solution.aim.y.abs() < EPSILON);
+    }
+
+    #[test]
+    fn target_below_origin() {
+        let origin = Vec3::new(0., 10., 0.);
+        let target = Vec3::ZERO;
+        let speed = 5.;
+
+        let BallisticSolutions::Two { low, high } = solve_launch(origin, target, speed, GRAVITY)
+        else {
+            panic!("Expected two solutions");
+        };
+
+        assert_hits(origin, target, speed, low);
+        assert_hits(origin, target, speed, high);
+    }
+
+    #[test]
+    fn impact_on_plane_is_inverse_of_solver() {
+        let origin = Vec3::new(0., 1., 0.);
+        let target = Vec3::new(12., 0., -7.);
+        let speed = 15.;
+
+        let solution = solve_launch(origin, target, speed, GRAVITY).high().unwrap();
+        let trajectory = Trajectory::from_aim(origin, solution.aim, speed, GRAVITY);
+        let impact = trajectory
+            .impact_on_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+            .unwrap();
+
+        assert!(impact.point.distance(target) < EPSILON);
+        assert!((impact.time - solution.flight_time).abs() < EPSILON);
+    }
+
+    #[test]
+    fn solution_drives_linear_stepper() {
+        let solution = solve_launch(Vec3::ZERO, Vec3::new(5., 0., 5.), 20., GRAVITY)
+            .low()
+            .unwrap();
+
+        let mut aim = LinearStepper::new(PitchYaw::default(), 100.);
+        aim.set_target(solution.aim);
+        aim.tick(Duration::from_secs(1));
+
+        assert!(aim.get().distance(&solution.aim) < EPSILON);
+    }
+}
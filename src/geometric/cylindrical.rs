@@ -0,0 +1,155 @@
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul, Sub},
+};
+
+/// Cylindrical coordinates around the Y axis: a yaw, a height and a distance from the
+/// axis.
+///
+/// Yaw follows the [`PitchYaw`](super::pitchyaw::PitchYaw) conventions: zero yaw
+/// points along -Z and positive yaw turns towards -X. Yaw wraps around [-PI, PI] and
+/// the radius is never negative.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Cylindrical {
+    /// Yaw rotation
+    pub y: f32,
+    /// Height along the Y axis
+    pub h: f32,
+    /// Distance from the Y axis
+    pub r: f32,
+}
+
+impl Cylindrical {
+    pub fn new(yaw: f32, height: f32, radius: f32) -> Self {
+        Self {
+            y: yaw,
+            h: height,
+            r: radius,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec3 {
+        Vec3::new(-self.y.sin() * self.r, self.h, -self.y.cos() * self.r)
+    }
+
+    /// Points on the Y axis map to zero yaw
+    pub fn from_vec(vec: Vec3) -> Self {
+        let radius = Vec2::new(vec.x, vec.z).length();
+        let yaw = if radius == 0. {
+            0.
+        } else {
+            (-vec.x).atan2(-vec.z)
+        };
+
+        Self::new(yaw, vec.y, radius)
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self {
+            y: (self.y + PI).rem_euclid(2. * PI) - PI,
+            h: self.h,
+            r: self.r.max(0.),
+        }
+    }
+
+    /// Takes into account the wrapping of yaw, in the same way as
+    /// `PitchYaw::sub_pitchyaw` does
+    pub fn sub_cylindrical(self, other: Self) -> Self {
+        let yaw = (self.y - other.y + PI).rem_euclid(2. * PI) - PI;
+
+        Self::new(yaw, self.h - other.h, self.r - other.r)
+    }
+
+    /// Moves each component towards `target` by at most the matching component of
+    /// `delta`: yaw (`x`), height (`y`) and radius (`z`)
+    pub fn step_toward_per_axis(&self, target: Cylindrical, delta: Vec3) -> Self {
+        let diff = target.sub_cylindrical(*self);
+        let step = |current: f32, target: f32, diff: f32, max: f32| {
+            if diff.abs() < max {
+                target
+            } else {
+                current + max * diff.signum()
+            }
+        };
+
+        Self::new(
+            step(self.y, target.y, diff.y, delta.x),
+            step(self.h, target.h, diff.h, delta.y),
+            step(self.r, target.r, diff.r, delta.z),
+        )
+        .normalize()
+    }
+}
+
+impl From<Vec3> for Cylindrical {
+    fn from(vec: Vec3) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl From<Cylindrical> for Vec3 {
+    fn from(value: Cylindrical) -> Self {
+        value.to_vec()
+    }
+}
+
+impl Sub<Cylindrical> for Cylindrical {
+    type Output = Cylindrical;
+
+    fn sub(self, rhs: Cylindrical) -> Self::Output {
+        self.sub_cylindrical(rhs)
+    }
+}
+
+impl Add<Cylindrical> for Cylindrical {
+    type Output = Cylindrical;
+
+    fn add(self, rhs: Cylindrical) -> Self::Output {
+        Cylindrical::new(self.y + rhs.y, self.h + rhs.h, self.r + rhs.r)
+    }
+}
+
+impl Mul<f32> for Cylindrical {
+    type Output = Cylindrical;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Cylindrical::new(self.y * rhs, self.h * rhs, self.r * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::PitchYaw;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn vec_round_trip() {
+        let vec = Vec3::new(4., -1.5, -2.);
+        let cylindrical = Cylindrical::from_vec(vec);
+
+        assert!((cylindrical.h - vec.y).abs() < EPSILON);
+        assert!(cylindrical.to_vec().distance(vec) < EPSILON);
+    }
+
+    #[test]
+    fn yaw_matches_pitchyaw() {
+        let yaw = 2.3;
+        let horizontal = Cylindrical::new(yaw, 0., 1.).to_vec();
+
+        assert!(horizontal.distance(PitchYaw::new(yaw, 0.).to_unit_vec()) < EPSILON);
+    }
+
+    #[test]
+    fn wrapped_sub_takes_short_way() {
+        let a = Cylindrical::new(PI - 0.1, 1., 2.);
+        let b = Cylindrical::new(-PI + 0.1, 3., 2.);
+
+        let delta = b - a;
+        assert!((delta.y - 0.2).abs() < EPSILON);
+        assert!((delta.h - 2.).abs() < EPSILON);
+    }
+}
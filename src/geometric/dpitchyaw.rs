@@ -0,0 +1,79 @@
+use bevy::math::{DQuat, DVec2, DVec3};
+
+use super::{
+    dpitchyawclamped::DPitchYawClamped,
+    pitchyaw::{PitchYaw, pitchyaw_type},
+};
+
+pitchyaw_type!(
+    /// Double-precision version of [`PitchYaw`]. Rotation without roll component. Wraps
+    /// yaw around [-PI, PI] and clamps pitch to [-PI/2, PI/2]
+    DPitchYaw, DPitchYawClamped, f64, DVec2, DVec3, DQuat
+);
+
+impl DPitchYaw {
+    pub fn as_pitchyaw(&self) -> PitchYaw {
+        PitchYaw::new(self.y as f32, self.p as f32)
+    }
+}
+
+impl From<PitchYaw> for DPitchYaw {
+    fn from(value: PitchYaw) -> Self {
+        value.as_dpitchyaw()
+    }
+}
+
+impl From<DPitchYaw> for PitchYaw {
+    fn from(value: DPitchYaw) -> Self {
+        value.as_pitchyaw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn dir_to_spherical_to_dir_is_identity() {
+        let dir = DVec3::new(223.3452, 5.22, 835.519);
+        let spherical = DPitchYaw::from_vec(dir);
+        let dir2 = spherical.to_unit_vec();
+
+        let dist = dir.normalize().distance(dir2);
+
+        assert!(
+            dist < EPSILON,
+            "Distance {dir} to {dir2} was {dist}, greater than allowed {EPSILON}"
+        );
+    }
+
+    #[test]
+    fn step_toward_near_wrap() {
+        let spherical_a = DPitchYaw::new(PI - 0.1, 0.);
+        let spherical_b = DPitchYaw::new(-PI + 0.1, 0.);
+
+        let stepped = spherical_a.step_toward(spherical_b, 0.05);
+        let target = DPitchYaw::new(PI - 0.05, 0.);
+
+        assert!(stepped.distance(&target) < EPSILON);
+    }
+
+    #[test]
+    fn angular_distance_near_pole_is_small() {
+        let a = DPitchYaw::new(0., PI / 2. - 0.05);
+        let b = DPitchYaw::new(PI - 0.01, PI / 2. - 0.05);
+
+        assert!(a.angular_distance(&b) < 0.11);
+    }
+
+    #[test]
+    fn converts_to_and_from_f32() {
+        let spherical = PitchYaw::new(1.25, -0.5);
+
+        assert_eq!(PitchYaw::from(DPitchYaw::from(spherical)), spherical);
+    }
+}
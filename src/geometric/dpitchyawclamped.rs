@@ -0,0 +1,69 @@
+use bevy::math::{DQuat, DVec2, DVec3};
+
+use super::{
+    dpitchyaw::DPitchYaw,
+    pitchyawclamped::{PitchYawClamped, pitchyaw_clamped_type},
+};
+
+pitchyaw_clamped_type!(
+    /// Double-precision version of [`PitchYawClamped`]. Rotation without roll component.
+    /// Clamps in both axes
+    DPitchYawClamped, DPitchYaw, f64, DVec2, DVec3, DQuat
+);
+
+impl DPitchYawClamped {
+    pub fn as_pitchyaw_clamped(&self) -> PitchYawClamped {
+        PitchYawClamped::new_with_clamps(
+            self.y as f32,
+            self.p as f32,
+            self.clamp_p as f32,
+            self.clamp_y as f32,
+        )
+    }
+}
+
+impl From<PitchYawClamped> for DPitchYawClamped {
+    fn from(value: PitchYawClamped) -> Self {
+        value.as_dpitchyaw_clamped()
+    }
+}
+
+impl From<DPitchYawClamped> for PitchYawClamped {
+    fn from(value: DPitchYawClamped) -> Self {
+        value.as_pitchyaw_clamped()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let spherical = DPitchYawClamped::new(8.21694, 12.032);
+
+        assert_eq!(spherical.normalize(), spherical.normalize().normalize());
+    }
+
+    #[test]
+    fn pi_spherical_is_pos_z() {
+        let spherical = DPitchYawClamped::new(PI, 0.);
+        let dist = spherical.to_unit_vec().distance(DVec3::Z);
+
+        assert!(dist < EPSILON);
+    }
+
+    #[test]
+    fn converts_to_and_from_f32_keeping_clamps() {
+        let spherical = PitchYawClamped::new_with_clamps(0.25, -0.5, 1., 2.);
+
+        assert_eq!(
+            PitchYawClamped::from(DPitchYawClamped::from(spherical)),
+            spherical
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ballistics::solve_launch, pitchyaw::PitchYaw};
+
+/// Flight time tolerance at which [`solve_intercept_iterative`] stops refining
+const TIME_TOLERANCE: f32 = 1e-4;
+
+/// Aim that makes a projectile meet a moving target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InterceptSolution {
+    pub aim: PitchYaw,
+    /// Launch direction, normalized
+    pub direction: Vec3,
+    /// Time until the projectile meets the target
+    pub time: f32,
+    /// Point where the projectile meets the target
+    pub point: Vec3,
+}
+
+/// Finds the direction to fire a projectile from `shooter` at `projectile_speed` so that
+/// it meets a target at `target` moving at a constant `target_velocity`, ignoring
+/// gravity. Returns `None` if the projectile cannot catch the target.
+pub fn solve_intercept(
+    shooter: Vec3,
+    projectile_speed: f32,
+    target: Vec3,
+    target_velocity: Vec3,
+) -> Option<InterceptSolution> {
+    let delta = target - shooter;
+
+    // |delta + target_velocity * t| = projectile_speed * t
+    let a = target_velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2. * delta.dot(target_velocity);
+    let c = delta.length_squared();
+
+    let time = if a.abs() < 1e-9 {
+        // Same speed as the target, only one root
+        if b.abs() < 1e-9 {
+            return None;
+        }
+        -c / b
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let t0 = (-b - root) / (2. * a);
+        let t1 = (-b + root) / (2. * a);
+        [t0.min(t1), t0.max(t1)].into_iter().find(|t| *t > 0.)?
+    };
+    if time <= 0. {
+        return None;
+    }
+
+    let point = target + target_velocity * time;
+    let direction = (point - shooter).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return None;
+    }
+
+    Some(InterceptSolution {
+        aim: PitchYaw::from_vec(direction),
+        direction,
+        time,
+        point,
+    })
+}
+
+/// Like [`solve_intercept`], but also accounts for the target's acceleration and for a
+/// gravity of magnitude `gravity` pointing down the Y axis, firing on the low arc.
+///
+/// The flight time is refined iteratively, for at most `max_iterations` steps. Returns
+/// `None` if the target is out of range or the iteration does not converge.
+pub fn solve_intercept_iterative(
+    shooter: Vec3,
+    projectile_speed: f32,
+    target: Vec3,
+    target_velocity: Vec3,
+    target_acceleration: Vec3,
+    gravity: f32,
+    max_iterations: usize,
+) -> Option<InterceptSolution> {
+    let predict =
+        |time: f32| target + target_velocity * time + target_acceleration * (0.5 * time * time);
+
+    let mut time = solve_intercept(shooter, projectile_speed, target, target_velocity)
+        .map(|solution| solution.time)
+        .unwrap_or_else(|| shooter.distance(target) / projectile_speed);
+
+    for _ in 0..max_iterations {
+        let point = predict(time);
+        let solution = solve_launch(shooter, point, projectile_speed, gravity).low()?;
+
+        if (solution.flight_time - time).abs() < TIME_TOLERANCE {
+            return Some(InterceptSolution {
+                aim: solution.aim,
+                direction: solution.aim.to_unit_vec(),
+                time: solution.flight_time,
+                point,
+            });
+        }
+        time = solution.flight_time;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometric::ballistics::Trajectory;
+
+    const EPSILON: f32 = 0.001;
+
+    #[test]
+    fn projectile_meets_moving_target() {
+        let shooter = Vec3::new(0., 1., 0.);
+        let target = Vec3::new(10., 1., -20.);
+        let velocity = Vec3::new(-3., 0., 2.);
+        let speed = 30.;
+
+        let solution = solve_intercept(shooter, speed, target, velocity).unwrap();
+        let projectile = shooter + solution.direction * speed * solution.time;
+        let target_then = target + velocity * solution.time;
+
+        assert!(projectile.distance(target_then) < EPSILON);
+        assert!(solution.aim.to_unit_vec().distance(solution.direction) < EPSILON);
+    }
+
+    #[test]
+    fn faster_target_running_away_has_no_solution() {
+        let solution = solve_intercept(
+            Vec3::ZERO,
+            5.,
+            Vec3::new(0., 0., -10.),
+            Vec3::new(0., 0., -10.),
+        );
+
+        assert!(solution.is_none());
+    }
+
+    #[test]
+    fn faster_target_approaching_can_be_hit() {
+        let solution = solve_intercept(
+            Vec3::ZERO,
+            5.,
+            Vec3::new(0., 0., -10.),
+            Vec3::new(0., 0., 10.),
+        );
+
+        assert!((solution.unwrap().time - 10. / 15.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn iterative_accounts_for_gravity_and_acceleration() {
+        let shooter = Vec3::ZERO;
+        let target = Vec3::new(30., 0., -10.);
+        let velocity = Vec3::new(0., 0., 4.);
+        let acceleration = Vec3::new(-1., 0., 0.);
+        let speed = 40.;
+        let gravity = 9.81;
+
+        let solution =
+            solve_intercept_iterative(shooter, speed, target, velocity, acceleration, gravity, 20)
+                .unwrap();
+
+        let trajectory = Trajectory::from_aim(shooter, solution.aim, speed, gravity);
+        let projectile = trajectory.position_at(solution.time);
+        let target_then =
+            target + velocity * solution.time + acceleration * (0.5 * solution.time.powi(2));
+
+        assert!(
+            projectile.distance(target_then) < 0.01,
+            "Missed by {}",
+            projectile.distance(target_then)
+        );
+    }
+}
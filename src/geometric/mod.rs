@@ -1,2 +1,10 @@
+pub mod angle;
+pub mod ballistics;
+pub mod cylindrical;
+pub mod dpitchyaw;
+pub mod dpitchyawclamped;
+pub mod intercept;
 pub mod pitchyaw;
 pub mod pitchyawclamped;
+pub mod spherical;
+pub mod swing_twist;
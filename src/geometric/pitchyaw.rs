@@ -1,161 +1,241 @@
 use bevy::math::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::{
-    f32::consts::PI,
-    ops::{Add, Mul, Sub},
-};
-
-use super::pitchyawclamped::PitchYawClamped;
-
-/// Rotation without roll component. Wraps yaw around [-PI, PI] and
-/// clamps pitch to [-PI/2, PI/2]
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub struct PitchYaw {
-    /// Pitch rotation
-    pub p: f32,
-    /// Yaw rotation
-    pub y: f32,
-}
-
-impl Default for PitchYaw {
-    fn default() -> Self {
-        Self::new(0., 0.)
-    }
-}
 
-impl PitchYaw {
-    pub fn new(u: f32, v: f32) -> Self {
-        Self { y: u, p: v }
-    }
-
-    pub fn to_unit_vec(&self) -> Vec3 {
-        sample_unit_sphere_surface(self.y, self.p)
-    }
-
-    pub fn from_vec(dir: Vec3) -> Self {
-        let u = (-dir.x).atan2(-dir.z);
-        let v = (dir.y / dir.length()).asin();
+use super::{dpitchyaw::DPitchYaw, pitchyawclamped::PitchYawClamped};
+
+/// Defines a pitch-yaw type and its free functions for one float precision, so that
+/// [`PitchYaw`] and [`DPitchYaw`] share a single implementation
+macro_rules! pitchyaw_type {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $clamped:ident, $f:ident, $vec2:ident, $vec3:ident, $quat:ident
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct $name {
+            /// Pitch rotation
+            pub p: $f,
+            /// Yaw rotation
+            pub y: $f,
+        }
 
-        Self::new(u, v)
-    }
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new(0., 0.)
+            }
+        }
 
-    pub fn length(&self) -> f32 {
-        (self.y * self.y + self.p * self.p).sqrt()
-    }
+        impl $name {
+            pub fn new(u: $f, v: $f) -> Self {
+                Self { y: u, p: v }
+            }
+
+            pub fn to_unit_vec(&self) -> $vec3 {
+                sample_unit_sphere_surface(self.y, self.p)
+            }
+
+            pub fn from_vec(dir: $vec3) -> Self {
+                let u = (-dir.x).atan2(-dir.z);
+                let v = (dir.y / dir.length()).asin();
+
+                Self::new(u, v)
+            }
+
+            pub fn length(&self) -> $f {
+                (self.y * self.y + self.p * self.p).sqrt()
+            }
+
+            pub fn distance(&self, other: &Self) -> $f {
+                (*self - *other).length()
+            }
+
+            /// Great-circle angle between the directions represented by `self` and
+            /// `other`.
+            ///
+            /// Unlike [`Self::distance`], which measures in angle space, this does not
+            /// overestimate distances near the poles.
+            pub fn angular_distance(&self, other: &Self) -> $f {
+                angle_between_unit_vecs(self.to_unit_vec(), other.to_unit_vec())
+            }
+
+            /// Angle between the direction represented by `self` and `dir`. `dir` does
+            /// not need to be normalized, but must be non-zero.
+            pub fn angle_between(&self, dir: $vec3) -> $f {
+                angle_between_unit_vecs(self.to_unit_vec(), dir.normalize())
+            }
+
+            /// Spherical linear interpolation: moves along the great circle between
+            /// `self` and `other`, at constant angular speed as `t` goes from 0 to 1.
+            pub fn slerp(&self, other: Self, t: $f) -> Self {
+                let dir = self.to_unit_vec().slerp(other.to_unit_vec(), t);
+                Self::from_vec(dir)
+            }
+
+            /// Alias of [`Self::slerp`].
+            pub fn lerp_geodesic(&self, other: Self, t: $f) -> Self {
+                self.slerp(other, t)
+            }
+
+            pub fn clamp_u(&self, min: $f, max: $f) -> Self {
+                Self::new(self.y.clamp(min, max), self.p)
+            }
+
+            pub fn clamp_v(&self, min: $f, max: $f) -> Self {
+                let v = self.p.clamp(min, max);
+                Self::new(self.y, v)
+            }
+
+            pub fn clamp(&self, min: $f, max: $f) -> Self {
+                self.clamp_u(min, max).clamp_v(min, max)
+            }
+
+            pub fn normalize(&self) -> Self {
+                use std::$f::consts::PI;
+
+                Self {
+                    p: self.p.clamp(-PI / 2., PI / 2.),
+                    y: (self.y + PI).rem_euclid(2. * PI) - PI,
+                }
+            }
+
+            pub fn flip(&self) -> Self {
+                use std::$f::consts::PI;
+
+                Self::new(
+                    2. * PI - (PI + self.y) - PI,
+                    (PI - (PI / 2. + self.p)) - PI / 2.,
+                )
+            }
+
+            /// Takes into account the wrapping of yaw
+            pub fn sub_pitchyaw(self, other: Self) -> Self {
+                use std::$f::consts::PI;
+
+                let pitch = self.p - other.p;
+
+                // Note that simply subtracting two yaws will be incorrect,
+                // as the resulting delta will never "cross" the y = PI boundary
+                let yaw = (self.y - other.y + PI).rem_euclid(2. * PI) - PI;
+
+                Self::new(yaw, pitch)
+            }
+
+            pub fn step_toward(&self, target: $name, dangle: $f) -> Self {
+                self.step_toward_per_axis(target, $vec2::splat(dangle))
+            }
+
+            /// Like `step_toward`, with separate maximum steps for yaw (`x`) and pitch
+            /// (`y`)
+            pub fn step_toward_per_axis(&self, target: $name, dangle: $vec2) -> Self {
+                let mut out = $name::default();
+                let delta = target.sub_pitchyaw(*self);
+
+                if delta.y.abs() < dangle.x {
+                    out.y = target.y;
+                } else {
+                    out.y = self.y + dangle.x * delta.y.signum();
+                }
+
+                if delta.p.abs() < dangle.y {
+                    out.p = target.p;
+                } else {
+                    out.p = self.p + dangle.y * delta.p.signum();
+                }
+
+                out.normalize()
+            }
+
+            pub fn to_quat(&self) -> $quat {
+                $quat::from_rotation_y(self.y) * $quat::from_rotation_x(-self.p)
+            }
+        }
 
-    pub fn distance(&self, other: &Self) -> f32 {
-        (*self - *other).length()
-    }
+        impl From<$vec3> for $name {
+            fn from(dir: $vec3) -> Self {
+                Self::from_vec(dir)
+            }
+        }
 
-    pub fn clamp_u(&self, min: f32, max: f32) -> Self {
-        Self::new(self.y.clamp(min, max), self.p)
-    }
+        impl ::std::ops::Sub<$name> for $name {
+            type Output = $name;
 
-    pub fn clamp_v(&self, min: f32, max: f32) -> Self {
-        let v = self.p.clamp(min, max);
-        Self::new(self.y, v)
-    }
+            fn sub(self, rhs: $name) -> Self::Output {
+                self.sub_pitchyaw(rhs)
+            }
+        }
 
-    pub fn clamp(&self, min: f32, max: f32) -> Self {
-        self.clamp_u(min, max).clamp_v(min, max)
-    }
+        impl ::std::ops::Add<$name> for $name {
+            type Output = $name;
 
-    pub fn normalize(&self) -> Self {
-        Self {
-            p: self.p.clamp(-PI / 2., PI / 2.),
-            y: (self.y + PI).rem_euclid(2. * PI) - PI,
+            fn add(self, rhs: $name) -> Self::Output {
+                $name::new(self.y + rhs.y, self.p + rhs.p)
+            }
         }
-    }
 
-    pub fn flip(&self) -> Self {
-        Self::new(
-            2. * PI - (PI + self.y) - PI,
-            (PI - (PI / 2. + self.p)) - PI / 2.,
-        )
-    }
-
-    /// Takes into account the wrapping of yaw
-    pub fn sub_pitchyaw(self, other: Self) -> Self {
-        let pitch = self.p - other.p;
+        impl ::std::ops::Mul<$f> for $name {
+            type Output = $name;
 
-        // None that simply subtracting two yaws will be incorrect,
-        // as the resulting delta will never "cross" the y = PI boundary
-        let yaw = (self.y - other.y + PI).rem_euclid(2. * PI) - PI;
+            fn mul(self, rhs: $f) -> Self::Output {
+                $name::new(self.y * rhs, self.p * rhs)
+            }
+        }
 
-        Self::new(yaw, pitch)
-    }
+        impl From<$clamped> for $name {
+            fn from(value: $clamped) -> Self {
+                Self::new(value.y, value.p)
+            }
+        }
 
-    pub fn step_toward(&self, target: PitchYaw, dangle: f32) -> Self {
-        let mut out = PitchYaw::default();
-        let delta = target.sub_pitchyaw(*self);
+        pub fn sample_unit_sphere_surface(u: $f, v: $f) -> $vec3 {
+            let horizontal_y = v.sin();
+            let xz_factor = v.cos();
+            let horizontal_x = -u.sin() * xz_factor;
+            let horizontal_z = -u.cos() * xz_factor;
 
-        if delta.y.abs() < dangle {
-            out.y = target.y;
-        } else {
-            out.y = self.y + dangle * delta.y.signum();
+            $vec3::new(horizontal_x, horizontal_y, horizontal_z)
         }
 
-        if delta.p.abs() < dangle {
-            out.p = target.p;
-        } else {
-            out.p = self.p + dangle * delta.p.signum();
+        /// Angle between two unit vectors. More accurate than `acos` of the dot product
+        /// for nearly parallel vectors.
+        pub fn angle_between_unit_vecs(a: $vec3, b: $vec3) -> $f {
+            a.cross(b).length().atan2(a.dot(b))
         }
-
-        out.normalize()
-    }
-
-    pub fn to_quat(&self) -> Quat {
-        Quat::from_rotation_y(self.y) * Quat::from_rotation_x(-self.p)
-    }
+    };
 }
 
-impl From<Vec3> for PitchYaw {
-    fn from(dir: Vec3) -> Self {
-        Self::from_vec(dir)
-    }
-}
+pub(super) use pitchyaw_type;
 
-impl Sub<PitchYaw> for PitchYaw {
-    type Output = PitchYaw;
+pitchyaw_type!(
+    /// Rotation without roll component. Wraps yaw around [-PI, PI] and
+    /// clamps pitch to [-PI/2, PI/2]
+    PitchYaw, PitchYawClamped, f32, Vec2, Vec3, Quat
+);
 
-    fn sub(self, rhs: PitchYaw) -> Self::Output {
-        self.sub_pitchyaw(rhs)
+impl PitchYaw {
+    pub fn to_dir3(&self) -> Dir3 {
+        Dir3::new_unchecked(self.to_unit_vec())
     }
-}
-
-impl Add<PitchYaw> for PitchYaw {
-    type Output = PitchYaw;
 
-    fn add(self, rhs: PitchYaw) -> Self::Output {
-        PitchYaw::new(self.y + rhs.y, self.p + rhs.p)
+    pub fn as_dpitchyaw(&self) -> DPitchYaw {
+        DPitchYaw::new(self.y as f64, self.p as f64)
     }
 }
 
-impl Mul<f32> for PitchYaw {
-    type Output = PitchYaw;
-
-    fn mul(self, rhs: f32) -> Self::Output {
-        PitchYaw::new(self.y * rhs, self.p * rhs)
+impl From<Dir3> for PitchYaw {
+    fn from(dir: Dir3) -> Self {
+        Self::from_vec(*dir)
     }
 }
 
-impl From<PitchYawClamped> for PitchYaw {
-    fn from(value: PitchYawClamped) -> Self {
-        Self::new(value.y, value.p)
+impl From<PitchYaw> for Dir3 {
+    fn from(value: PitchYaw) -> Self {
+        value.to_dir3()
     }
 }
 
-pub fn sample_unit_sphere_surface(u: f32, v: f32) -> Vec3 {
-    let horizontal_y = v.sin();
-    let xz_factor = v.cos();
-    let horizontal_x = -u.sin() * xz_factor;
-    let horizontal_z = -u.cos() * xz_factor;
-
-    Vec3::new(horizontal_x, horizontal_y, horizontal_z)
-}
-
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::PI;
+
     use super::*;
 
     const EPSILON: f32 = 0.0001;
@@ -283,4 +363,111 @@ mod tests {
         assert!(spherical_b.distance(&applied) < EPSILON);
         assert!(delta.length() - 0.2 < EPSILON);
     }
+
+    #[test]
+    fn angular_distance_matches_quat_angle_for_pure_yaw() {
+        let a = PitchYaw::new(PI - 0.3, 0.);
+        let b = PitchYaw::new(-PI + 0.2, 0.);
+
+        let angle = a.angular_distance(&b);
+        let quat_angle = a.to_quat().angle_between(b.to_quat());
+
+        assert!(
+            (angle - quat_angle).abs() < EPSILON,
+            "Angular distance {angle} differs from quat angle {quat_angle}"
+        );
+        assert!((angle - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn angular_distance_matches_quat_angle_for_pure_pitch() {
+        let a = PitchYaw::new(1.2, -0.4);
+        let b = PitchYaw::new(1.2, 0.9);
+
+        let angle = a.angular_distance(&b);
+        let quat_angle = a.to_quat().angle_between(b.to_quat());
+
+        assert!(
+            (angle - quat_angle).abs() < EPSILON,
+            "Angular distance {angle} differs from quat angle {quat_angle}"
+        );
+    }
+
+    #[test]
+    fn angular_distance_bounded_by_quat_angle() {
+        let a = PitchYaw::new(0.7, 0.3);
+        let b = PitchYaw::new(-2.1, 1.1);
+
+        let angle = a.angular_distance(&b);
+        let quat_angle = a.to_quat().angle_between(b.to_quat());
+
+        // The quaternion angle includes the roll needed to go from one frame to the
+        // other, so it can only be larger than the angle between the directions
+        assert!(
+            angle <= quat_angle + EPSILON,
+            "Angular distance {angle} exceeds quat angle {quat_angle}"
+        );
+    }
+
+    #[test]
+    fn angular_distance_near_pole_is_small() {
+        let a = PitchYaw::new(0., PI / 2. - 0.05);
+        let b = PitchYaw::new(PI - 0.01, PI / 2. - 0.05);
+
+        let angle = a.angular_distance(&b);
+
+        assert!(angle < 0.11, "Angular distance {angle} across the pole");
+        assert!(a.distance(&b) > 3.);
+    }
+
+    #[test]
+    fn angle_between_matches_angular_distance() {
+        let a = PitchYaw::new(0.4, -0.2);
+        let b = PitchYaw::new(-1.3, 0.5);
+
+        let angle = a.angle_between(b.to_unit_vec() * 3.);
+
+        assert!((angle - a.angular_distance(&b)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn slerp_matches_quat_slerp_for_pure_yaw() {
+        let a = PitchYaw::new(PI - 0.1, 0.);
+        let b = PitchYaw::new(-PI + 0.3, 0.);
+
+        let slerped = a.slerp(b, 0.25);
+        let quat_slerped = a.to_quat().slerp(b.to_quat(), 0.25);
+
+        let angle = slerped.to_quat().angle_between(quat_slerped);
+        assert!(angle < EPSILON, "Slerped {slerped:?} is {angle} away");
+    }
+
+    #[test]
+    fn slerp_moves_at_constant_angular_speed() {
+        let a = PitchYaw::new(0.3, 1.2);
+        let b = PitchYaw::new(-2.8, 1.0);
+        let total = a.angular_distance(&b);
+
+        for t in [0., 0.2, 0.5, 0.9, 1.] {
+            let slerped = a.slerp(b, t);
+            let from_a = a.angular_distance(&slerped);
+            let to_b = slerped.angular_distance(&b);
+
+            assert!(
+                (from_a - t * total).abs() < 0.001,
+                "At t = {t}, moved {from_a}, expected {}",
+                t * total
+            );
+            assert!((from_a + to_b - total).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn dir3_round_trip() {
+        let spherical = PitchYaw::new(-2.3, 0.7);
+        let dir = Dir3::from(spherical);
+
+        assert!(dir.distance(spherical.to_unit_vec()) < EPSILON);
+        assert!(PitchYaw::from(dir).distance(&spherical) < EPSILON);
+    }
 }
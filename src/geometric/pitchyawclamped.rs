@@ -1,169 +1,243 @@
 use bevy::math::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::{
-    f32::consts::PI,
-    ops::{Add, Mul, Sub},
-};
-
-use super::pitchyaw::PitchYaw;
-
-/// Rotation without roll component. Clamps in both axes
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub struct PitchYawClamped {
-    /// Pitch rotation
-    pub p: f32,
-    /// Yaw rotation
-    pub y: f32,
-    pub clamp_p: f32,
-    pub clamp_y: f32,
-}
 
-impl Default for PitchYawClamped {
-    fn default() -> Self {
-        Self::new(0., 0.)
-    }
-}
-
-impl PitchYawClamped {
-    pub fn new(u: f32, v: f32) -> Self {
-        Self {
-            y: u,
-            p: v,
-            clamp_p: PI / 2. - 0.001,
-            clamp_y: PI - 0.001,
+use super::{dpitchyawclamped::DPitchYawClamped, pitchyaw::PitchYaw};
+
+pub use super::pitchyaw::sample_unit_sphere_surface;
+
+/// Defines a clamped pitch-yaw type for one float precision, so that
+/// [`PitchYawClamped`] and [`DPitchYawClamped`] share a single implementation
+macro_rules! pitchyaw_clamped_type {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $pitchyaw:ident, $f:ident, $vec2:ident, $vec3:ident, $quat:ident
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct $name {
+            /// Pitch rotation
+            pub p: $f,
+            /// Yaw rotation
+            pub y: $f,
+            pub clamp_p: $f,
+            pub clamp_y: $f,
         }
-    }
 
-    pub fn new_with_clamps(u: f32, v: f32, clamp_p: f32, clamp_y: f32) -> Self {
-        Self {
-            y: u,
-            p: v,
-            clamp_p,
-            clamp_y,
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new(0., 0.)
+            }
         }
-    }
-
-    pub fn to_unit_vec(&self) -> Vec3 {
-        sample_unit_sphere_surface(self.y, self.p)
-    }
-
-    pub fn from_vec(dir: Vec3) -> Self {
-        let u = (-dir.x).atan2(-dir.z);
-        let v = (dir.y / dir.length()).asin();
-
-        Self::new(u, v)
-    }
-
-    pub fn length(&self) -> f32 {
-        (self.y * self.y + self.p * self.p).sqrt()
-    }
-
-    pub fn distance(&self, other: &Self) -> f32 {
-        (*self - *other).length()
-    }
-
-    pub fn clamp_u(&self, min: f32, max: f32) -> Self {
-        Self::new(self.y.clamp(min, max), self.p)
-    }
 
-    pub fn clamp_v(&self, min: f32, max: f32) -> Self {
-        let v = self.p.clamp(min, max);
-        Self::new(self.y, v)
-    }
-
-    pub fn clamp(&self, min: f32, max: f32) -> Self {
-        self.clamp_u(min, max).clamp_v(min, max)
-    }
-
-    pub fn normalize(&self) -> Self {
-        Self {
-            p: self.p.clamp(-self.clamp_p, self.clamp_p),
-            y: self.y.clamp(-self.clamp_y, self.clamp_y),
-            ..*self
+        impl $name {
+            pub fn new(u: $f, v: $f) -> Self {
+                use std::$f::consts::PI;
+
+                Self {
+                    y: u,
+                    p: v,
+                    clamp_p: PI / 2. - 0.001,
+                    clamp_y: PI - 0.001,
+                }
+            }
+
+            pub fn new_with_clamps(u: $f, v: $f, clamp_p: $f, clamp_y: $f) -> Self {
+                Self {
+                    y: u,
+                    p: v,
+                    clamp_p,
+                    clamp_y,
+                }
+            }
+
+            pub fn to_unit_vec(&self) -> $vec3 {
+                $pitchyaw::from(*self).to_unit_vec()
+            }
+
+            pub fn from_vec(dir: $vec3) -> Self {
+                $pitchyaw::from_vec(dir).into()
+            }
+
+            pub fn length(&self) -> $f {
+                (self.y * self.y + self.p * self.p).sqrt()
+            }
+
+            pub fn distance(&self, other: &Self) -> $f {
+                (*self - *other).length()
+            }
+
+            /// Great-circle angle between the directions represented by `self` and
+            /// `other`.
+            ///
+            /// Unlike [`Self::distance`], which measures in angle space, this does not
+            /// overestimate distances near the poles.
+            pub fn angular_distance(&self, other: &Self) -> $f {
+                $pitchyaw::from(*self).angular_distance(&$pitchyaw::from(*other))
+            }
+
+            /// Angle between the direction represented by `self` and `dir`. `dir` does
+            /// not need to be normalized, but must be non-zero.
+            pub fn angle_between(&self, dir: $vec3) -> $f {
+                $pitchyaw::from(*self).angle_between(dir)
+            }
+
+            /// Interpolates yaw and pitch linearly, within the clamps of `self`. Unlike
+            /// a slerp this does not follow the great circle, which could cross yaw ±PI
+            /// or leave the clamp range and make the result snap. The two agree for
+            /// pure pitch changes.
+            pub fn lerp_clamped(&self, other: Self, t: $f) -> Self {
+                let from = self.normalize();
+                let to = Self {
+                    clamp_p: self.clamp_p,
+                    clamp_y: self.clamp_y,
+                    ..other
+                }
+                .normalize();
+
+                Self {
+                    y: from.y + (to.y - from.y) * t,
+                    p: from.p + (to.p - from.p) * t,
+                    ..from
+                }
+            }
+
+            pub fn clamp_u(&self, min: $f, max: $f) -> Self {
+                Self::new(self.y.clamp(min, max), self.p)
+            }
+
+            pub fn clamp_v(&self, min: $f, max: $f) -> Self {
+                let v = self.p.clamp(min, max);
+                Self::new(self.y, v)
+            }
+
+            pub fn clamp(&self, min: $f, max: $f) -> Self {
+                self.clamp_u(min, max).clamp_v(min, max)
+            }
+
+            pub fn normalize(&self) -> Self {
+                Self {
+                    p: self.p.clamp(-self.clamp_p, self.clamp_p),
+                    y: self.y.clamp(-self.clamp_y, self.clamp_y),
+                    ..*self
+                }
+            }
+
+            /// Subtracts pitch and yaw separately. Yaw is clamped rather than wrapped,
+            /// so the difference never crosses the y = PI boundary
+            pub fn sub_pitchyaw(mut self, other: Self) -> Self {
+                self.p -= other.p;
+                self.y -= other.y;
+                self
+            }
+
+            pub fn step_toward(&self, target: $name, dangle: $f) -> Self {
+                self.step_toward_per_axis(target, $vec2::splat(dangle))
+            }
+
+            /// Like `step_toward`, with separate maximum steps for yaw (`x`) and pitch
+            /// (`y`)
+            pub fn step_toward_per_axis(&self, target: $name, dangle: $vec2) -> Self {
+                let mut out = $name::default();
+                let delta = target.sub_pitchyaw(*self);
+
+                if delta.y.abs() < dangle.x {
+                    out.y = target.y;
+                } else {
+                    out.y = self.y + dangle.x * delta.y.signum();
+                }
+
+                if delta.p.abs() < dangle.y {
+                    out.p = target.p;
+                } else {
+                    out.p = self.p + dangle.y * delta.p.signum();
+                }
+
+                out.clamp_y = self.clamp_y;
+                out.clamp_p = self.clamp_p;
+
+                out.normalize()
+            }
+
+            pub fn to_quat(&self) -> $quat {
+                $quat::from_rotation_y(self.y) * $quat::from_rotation_x(-self.p)
+            }
         }
-    }
 
-    /// Takes into account the wrapping of yaw
-    pub fn sub_pitchyaw(mut self, other: Self) -> Self {
-        self.p -= other.p;
-        self.y -= other.y;
-        self
-    }
-
-    pub fn step_toward(&self, target: PitchYawClamped, dangle: f32) -> Self {
-        let mut out = PitchYawClamped::default();
-        let delta = target.sub_pitchyaw(*self);
+        impl From<$pitchyaw> for $name {
+            fn from(value: $pitchyaw) -> Self {
+                Self::new(value.y, value.p)
+            }
+        }
 
-        if delta.y.abs() < dangle {
-            out.y = target.y;
-        } else {
-            out.y = self.y + dangle * delta.y.signum();
+        impl From<$vec3> for $name {
+            fn from(dir: $vec3) -> Self {
+                Self::from_vec(dir)
+            }
         }
 
-        if delta.p.abs() < dangle {
-            out.p = target.p;
-        } else {
-            out.p = self.p + dangle * delta.p.signum();
+        impl ::std::ops::Sub<$name> for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> Self::Output {
+                self.sub_pitchyaw(rhs)
+            }
         }
 
-        out.clamp_y = self.clamp_y;
-        out.clamp_p = self.clamp_p;
+        impl ::std::ops::Add<$name> for $name {
+            type Output = $name;
 
-        out.normalize()
-    }
+            fn add(self, rhs: $name) -> Self::Output {
+                $name::new_with_clamps(self.y + rhs.y, self.p + rhs.p, self.clamp_p, self.clamp_y)
+            }
+        }
 
-    pub fn to_quat(&self) -> Quat {
-        Quat::from_rotation_y(self.y) * Quat::from_rotation_x(-self.p)
-    }
-}
+        impl ::std::ops::Mul<$f> for $name {
+            type Output = $name;
 
-impl From<PitchYaw> for PitchYawClamped {
-    fn from(value: PitchYaw) -> Self {
-        Self::new(value.y, value.p)
-    }
+            fn mul(self, rhs: $f) -> Self::Output {
+                $name::new_with_clamps(self.y * rhs, self.p * rhs, self.clamp_p, self.clamp_y)
+            }
+        }
+    };
 }
 
-impl From<Vec3> for PitchYawClamped {
-    fn from(dir: Vec3) -> Self {
-        Self::from_vec(dir)
-    }
-}
+pub(super) use pitchyaw_clamped_type;
 
-impl Sub<PitchYawClamped> for PitchYawClamped {
-    type Output = PitchYawClamped;
+pitchyaw_clamped_type!(
+    /// Rotation without roll component. Clamps in both axes
+    PitchYawClamped, PitchYaw, f32, Vec2, Vec3, Quat
+);
 
-    fn sub(self, rhs: PitchYawClamped) -> Self::Output {
-        self.sub_pitchyaw(rhs)
+impl PitchYawClamped {
+    pub fn to_dir3(&self) -> Dir3 {
+        Dir3::new_unchecked(self.to_unit_vec())
     }
-}
-
-impl Add<PitchYawClamped> for PitchYawClamped {
-    type Output = PitchYawClamped;
 
-    fn add(self, rhs: PitchYawClamped) -> Self::Output {
-        PitchYawClamped::new_with_clamps(self.y + rhs.y, self.p + rhs.p, self.clamp_p, self.clamp_y)
+    pub fn as_dpitchyaw_clamped(&self) -> DPitchYawClamped {
+        DPitchYawClamped::new_with_clamps(
+            self.y as f64,
+            self.p as f64,
+            self.clamp_p as f64,
+            self.clamp_y as f64,
+        )
     }
 }
 
-impl Mul<f32> for PitchYawClamped {
-    type Output = PitchYawClamped;
-
-    fn mul(self, rhs: f32) -> Self::Output {
-        PitchYawClamped::new_with_clamps(self.y * rhs, self.p * rhs, self.clamp_p, self.clamp_y)
+impl From<Dir3> for PitchYawClamped {
+    fn from(dir: Dir3) -> Self {
+        Self::from_vec(*dir)
     }
 }
 
-pub fn sample_unit_sphere_surface(u: f32, v: f32) -> Vec3 {
-    let horizontal_y = v.sin();
-    let xz_factor = v.cos();
-    let horizontal_x = -u.sin() * xz_factor;
-    let horizontal_z = -u.cos() * xz_factor;
-
-    Vec3::new(horizontal_x, horizontal_y, horizontal_z)
+impl From<PitchYawClamped> for Dir3 {
+    fn from(value: PitchYawClamped) -> Self {
+        value.to_dir3()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::PI;
+
     use super::*;
 
     const EPSILON: f32 = 0.0001;
@@ -235,4 +309,71 @@ mod tests {
             spherical.normalize().normalize().normalize()
         );
     }
+
+    #[test]
+    fn angular_distance_matches_quat_angle_for_pure_pitch() {
+        let a = PitchYawClamped::new(-0.6, -0.4);
+        let b = PitchYawClamped::new(-0.6, 1.1);
+
+        let angle = a.angular_distance(&b);
+        let quat_angle = a.to_quat().angle_between(b.to_quat());
+
+        assert!(
+            (angle - quat_angle).abs() < EPSILON,
+            "Angular distance {angle} differs from quat angle {quat_angle}"
+        );
+    }
+
+    #[test]
+    fn angular_distance_bounded_by_quat_angle() {
+        let a = PitchYawClamped::new(1.3, 0.8);
+        let b = PitchYawClamped::new(-1.9, 1.2);
+
+        let angle = a.angular_distance(&b);
+        let quat_angle = a.to_quat().angle_between(b.to_quat());
+
+        assert!(
+            angle <= quat_angle + EPSILON,
+            "Angular distance {angle} exceeds quat angle {quat_angle}"
+        );
+    }
+
+    #[test]
+    fn lerp_clamped_matches_quat_slerp_for_pure_pitch() {
+        let a = PitchYawClamped::new(0.5, -0.7);
+        let b = PitchYawClamped::new(0.5, 0.9);
+
+        let lerped = a.lerp_clamped(b, 0.6);
+        let quat_slerped = a.to_quat().slerp(b.to_quat(), 0.6);
+
+        let angle = lerped.to_quat().angle_between(quat_slerped);
+        assert!(angle < EPSILON, "Lerped {lerped:?} is {angle} away");
+    }
+
+    #[test]
+    fn lerp_clamped_keeps_clamps() {
+        let a = PitchYawClamped::new_with_clamps(0., 0., 0.5, 1.);
+        let b = PitchYawClamped::new(2., 1.2);
+
+        let lerped = a.lerp_clamped(b, 1.);
+
+        assert_eq!(lerped.clamp_p, 0.5);
+        assert_eq!(lerped.clamp_y, 1.);
+        assert!(lerped.p <= 0.5 && lerped.y <= 1.);
+    }
+
+    #[test]
+    fn lerp_clamped_does_not_cross_yaw_wrap() {
+        let a = PitchYawClamped::new(3., 0.2);
+        let b = PitchYawClamped::new(-3., 0.2);
+
+        let mut previous = a;
+        for i in 1..=20 {
+            let lerped = a.lerp_clamped(b, i as f32 / 20.);
+            assert!(lerped.y.abs() <= a.clamp_y);
+            assert!(lerped.y < previous.y && previous.y - lerped.y < 0.5);
+            previous = lerped;
+        }
+        assert!(a.lerp_clamped(b, 0.5).y.abs() < EPSILON);
+    }
 }
@@ -0,0 +1,175 @@
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul, Sub},
+};
+
+use super::pitchyaw::{PitchYaw, sample_unit_sphere_surface};
+
+/// Spherical coordinates: a [`PitchYaw`] direction plus a distance from the origin.
+///
+/// Uses the same axis conventions as `PitchYaw`: zero yaw and pitch point along -Z,
+/// positive yaw turns towards -X and positive pitch towards +Y. Yaw wraps around
+/// [-PI, PI], pitch is clamped to [-PI/2, PI/2] and the radius is never negative.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Spherical {
+    /// Pitch rotation
+    pub p: f32,
+    /// Yaw rotation
+    pub y: f32,
+    /// Distance from the origin
+    pub r: f32,
+}
+
+impl Spherical {
+    pub fn new(yaw: f32, pitch: f32, radius: f32) -> Self {
+        Self {
+            y: yaw,
+            p: pitch,
+            r: radius,
+        }
+    }
+
+    pub fn from_pitchyaw(direction: PitchYaw, radius: f32) -> Self {
+        Self::new(direction.y, direction.p, radius)
+    }
+
+    pub fn to_pitchyaw(&self) -> PitchYaw {
+        PitchYaw::new(self.y, self.p)
+    }
+
+    pub fn to_vec(&self) -> Vec3 {
+        sample_unit_sphere_surface(self.y, self.p) * self.r
+    }
+
+    /// The zero vector maps to a zero radius looking down -Z
+    pub fn from_vec(vec: Vec3) -> Self {
+        let radius = vec.length();
+        if radius == 0. {
+            return Self::default();
+        }
+
+        Self::from_pitchyaw(PitchYaw::from_vec(vec), radius)
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self {
+            p: self.p.clamp(-PI / 2., PI / 2.),
+            y: (self.y + PI).rem_euclid(2. * PI) - PI,
+            r: self.r.max(0.),
+        }
+    }
+
+    /// Takes into account the wrapping of yaw, in the same way as
+    /// `PitchYaw::sub_pitchyaw` does
+    pub fn sub_spherical(self, other: Self) -> Self {
+        let yaw = (self.y - other.y + PI).rem_euclid(2. * PI) - PI;
+
+        Self::new(yaw, self.p - other.p, self.r - other.r)
+    }
+
+    /// Moves each component towards `target` by at most the matching component of
+    /// `delta`: yaw (`x`), pitch (`y`) and radius (`z`)
+    pub fn step_toward_per_axis(&self, target: Spherical, delta: Vec3) -> Self {
+        let diff = target.sub_spherical(*self);
+        let step = |current: f32, target: f32, diff: f32, max: f32| {
+            if diff.abs() < max {
+                target
+            } else {
+                current + max * diff.signum()
+            }
+        };
+
+        Self::new(
+            step(self.y, target.y, diff.y, delta.x),
+            step(self.p, target.p, diff.p, delta.y),
+            step(self.r, target.r, diff.r, delta.z),
+        )
+        .normalize()
+    }
+}
+
+impl From<Vec3> for Spherical {
+    fn from(vec: Vec3) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl From<Spherical> for Vec3 {
+    fn from(value: Spherical) -> Self {
+        value.to_vec()
+    }
+}
+
+impl Sub<Spherical> for Spherical {
+    type Output = Spherical;
+
+    fn sub(self, rhs: Spherical) -> Self::Output {
+        self.sub_spherical(rhs)
+    }
+}
+
+impl Add<Spherical> for Spherical {
+    type Output = Spherical;
+
+    fn add(self, rhs: Spherical) -> Self::Output {
+        Spherical::new(self.y + rhs.y, self.p + rhs.p, self.r + rhs.r)
+    }
+}
+
+impl Mul<f32> for Spherical {
+    type Output = Spherical;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Spherical::new(self.y * rhs, self.p * rhs, self.r * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{LinearStepper, SpringStepper, TickInterpolator, critical_damp_coeff};
+    use std::time::Duration;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn vec_round_trip() {
+        let vec = Vec3::new(-3.5, 2., 7.25);
+        let spherical = Spherical::from_vec(vec);
+
+        assert!((spherical.r - vec.length()).abs() < EPSILON);
+        assert!(spherical.to_vec().distance(vec) < EPSILON);
+    }
+
+    #[test]
+    fn matches_pitchyaw_direction() {
+        let direction = PitchYaw::new(2., -0.3);
+        let spherical = Spherical::from_pitchyaw(direction, 5.);
+
+        assert!(spherical.to_vec().distance(direction.to_unit_vec() * 5.) < EPSILON);
+    }
+
+    #[test]
+    fn steppers_take_the_short_way_around() {
+        let from = Spherical::new(PI - 0.1, 0., 10.);
+        let to = Spherical::new(-PI + 0.1, 0.2, 4.);
+
+        let mut linear = LinearStepper::new_with_speed(from, Vec3::new(0.1, 1., 1.));
+        linear.set_target(to);
+        linear.tick(Duration::from_secs(1));
+        let stepped = linear.get();
+        assert!((stepped.y.abs() - PI).abs() < EPSILON, "{stepped:?}");
+        assert!((stepped.p - 0.2).abs() < EPSILON);
+        assert!((stepped.r - 9.).abs() < EPSILON);
+
+        let mut spring = SpringStepper::new(from, 50., critical_damp_coeff(50.));
+        spring.set_target(to);
+        for _ in 0..200 {
+            spring.tick(Duration::from_millis(10));
+            assert!(spring.get().y.abs() > PI - 0.2, "{:?}", spring.get());
+        }
+        assert!(spring.get().to_vec().distance(to.to_vec()) < 0.001);
+    }
+}
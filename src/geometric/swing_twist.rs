@@ -0,0 +1,224 @@
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::{pitchyaw::PitchYaw, pitchyawclamped::PitchYawClamped};
+
+/// Rotation split into a twist about `axis` followed by a swing, which moves `axis`
+/// without rotating about it. The original rotation is `swing * twist`.
+///
+/// Conversions to and from [`PitchYaw`] describe the direction `axis` points to after
+/// the swing, so they are most useful when `axis` is the forward axis,
+/// [`Vec3::NEG_Z`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SwingTwist {
+    pub swing: Quat,
+    pub twist: Quat,
+    /// Twist axis, normalized
+    pub axis: Vec3,
+}
+
+impl Default for SwingTwist {
+    fn default() -> Self {
+        Self {
+            swing: Quat::IDENTITY,
+            twist: Quat::IDENTITY,
+            axis: Vec3::NEG_Z,
+        }
+    }
+}
+
+impl SwingTwist {
+    /// Decomposes `rotation` into a swing and a twist about `axis`
+    pub fn from_quat(rotation: Quat, axis: Vec3) -> Self {
+        let axis = axis.normalize();
+        let projected = rotation.xyz().dot(axis) * axis;
+        let twist = Quat::from_xyzw(projected.x, projected.y, projected.z, rotation.w);
+
+        // A swing of exactly PI leaves no twist information, any twist is valid
+        let twist = if twist.length_squared() < 1e-12 {
+            Quat::IDENTITY
+        } else {
+            twist.normalize()
+        };
+        let swing = rotation * twist.inverse();
+
+        Self { swing, twist, axis }
+    }
+
+    /// Builds a rotation about [`Vec3::NEG_Z`] that looks towards `dir` and is
+    /// twisted by `twist_angle` around it
+    pub fn from_pitchyaw(dir: PitchYaw, twist_angle: f32) -> Self {
+        Self::from_parts(Vec3::NEG_Z, dir.to_unit_vec(), twist_angle)
+    }
+
+    /// Builds a rotation that swings `axis` to point towards `dir` and twists it by
+    /// `twist_angle`
+    pub fn from_parts(axis: Vec3, dir: Vec3, twist_angle: f32) -> Self {
+        let axis = axis.normalize();
+        Self {
+            swing: Quat::from_rotation_arc(axis, dir.normalize()),
+            twist: Quat::from_axis_angle(axis, twist_angle),
+            axis,
+        }
+    }
+
+    pub fn to_quat(&self) -> Quat {
+        self.swing * self.twist
+    }
+
+    /// Signed twist angle about `axis`, in [-PI, PI]
+    pub fn twist_angle(&self) -> f32 {
+        let angle = 2. * self.twist.xyz().dot(self.axis).atan2(self.twist.w);
+        (angle + PI).rem_euclid(2. * PI) - PI
+    }
+
+    /// Angle by which the swing moves the twist axis
+    pub fn swing_angle(&self) -> f32 {
+        self.swing.angle_between(Quat::IDENTITY)
+    }
+
+    /// Direction the twist axis points to after the swing
+    pub fn swing_dir(&self) -> Vec3 {
+        self.swing * self.axis
+    }
+
+    pub fn swing_pitchyaw(&self) -> PitchYaw {
+        PitchYaw::from_vec(self.swing_dir())
+    }
+
+    pub fn swing_pitchyaw_clamped(&self) -> PitchYawClamped {
+        PitchYawClamped::from_vec(self.swing_dir())
+    }
+
+    /// Replaces the swing by one that moves the twist axis towards `dir`
+    pub fn with_swing_dir(&self, dir: Vec3) -> Self {
+        Self {
+            swing: Quat::from_rotation_arc(self.axis, dir.normalize()),
+            ..*self
+        }
+    }
+
+    pub fn with_twist_angle(&self, twist_angle: f32) -> Self {
+        Self {
+            twist: Quat::from_axis_angle(self.axis, twist_angle),
+            ..*self
+        }
+    }
+
+    /// Clamps the twist angle to [min, max]
+    pub fn clamp_twist(&self, min: f32, max: f32) -> Self {
+        self.with_twist_angle(self.twist_angle().clamp(min, max))
+    }
+
+    /// Clamps the swing direction in the same way [`PitchYawClamped::normalize`]
+    /// clamps directions, using the clamps of `limits`
+    pub fn clamp_swing(&self, limits: PitchYawClamped) -> Self {
+        let PitchYawClamped { y, p, .. } = self.swing_pitchyaw_clamped();
+        let clamped =
+            PitchYawClamped::new_with_clamps(y, p, limits.clamp_p, limits.clamp_y).normalize();
+        self.with_swing_dir(clamped.to_unit_vec())
+    }
+
+    /// Clamps both the swing direction and the twist angle, see
+    /// [`SwingTwist::clamp_swing`] and [`SwingTwist::clamp_twist`]
+    pub fn clamp(&self, limits: PitchYawClamped, twist_min: f32, twist_max: f32) -> Self {
+        self.clamp_swing(limits).clamp_twist(twist_min, twist_max)
+    }
+}
+
+impl From<SwingTwist> for Quat {
+    fn from(value: SwingTwist) -> Self {
+        value.to_quat()
+    }
+}
+
+impl From<SwingTwist> for PitchYaw {
+    fn from(value: SwingTwist) -> Self {
+        value.swing_pitchyaw()
+    }
+}
+
+impl From<SwingTwist> for PitchYawClamped {
+    fn from(value: SwingTwist) -> Self {
+        value.swing_pitchyaw_clamped()
+    }
+}
+
+/// Limits the twist of `rotation` about `axis` to [min, max], keeping its swing
+pub fn clamp_twist(rotation: Quat, axis: Vec3, min: f32, max: f32) -> Quat {
+    SwingTwist::from_quat(rotation, axis)
+        .clamp_twist(min, max)
+        .to_quat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn decomposition_recombines_to_original() {
+        let rotation = Quat::from_euler(EulerRot::YXZ, 0.7, -0.4, 1.1);
+        let decomposed = SwingTwist::from_quat(rotation, Vec3::NEG_Z);
+
+        let angle = decomposed.to_quat().angle_between(rotation);
+        assert!(angle < EPSILON, "Recombined rotation is {angle} away");
+    }
+
+    #[test]
+    fn swing_does_not_rotate_about_axis() {
+        let rotation = Quat::from_euler(EulerRot::YXZ, -1.3, 0.5, 0.8);
+        let decomposed = SwingTwist::from_quat(rotation, Vec3::Y);
+
+        let swing_twist = SwingTwist::from_quat(decomposed.swing, Vec3::Y);
+        assert!(swing_twist.twist_angle().abs() < EPSILON);
+    }
+
+    #[test]
+    fn pure_twist_is_all_twist() {
+        let rotation = Quat::from_axis_angle(Vec3::NEG_Z, 0.6);
+        let decomposed = SwingTwist::from_quat(rotation, Vec3::NEG_Z);
+
+        assert!((decomposed.twist_angle() - 0.6).abs() < EPSILON);
+        assert!(decomposed.swing_angle() < EPSILON);
+    }
+
+    #[test]
+    fn swing_matches_pitchyaw() {
+        let dir = PitchYaw::new(0.9, -0.3);
+        let decomposed = SwingTwist::from_pitchyaw(dir, 0.4);
+
+        let redecomposed = SwingTwist::from_quat(decomposed.to_quat(), Vec3::NEG_Z);
+        let swing = redecomposed.swing_pitchyaw();
+
+        assert!(swing.distance(&dir) < EPSILON, "Swing was {swing:?}");
+        assert!((redecomposed.twist_angle() - 0.4).abs() < EPSILON);
+    }
+
+    #[test]
+    fn clamp_twist_limits_twist_only() {
+        let dir = PitchYaw::new(-0.5, 0.2);
+        let rotation = SwingTwist::from_pitchyaw(dir, 1.2).to_quat();
+
+        let clamped =
+            SwingTwist::from_quat(clamp_twist(rotation, Vec3::NEG_Z, -0.5, 0.5), Vec3::NEG_Z);
+
+        assert!((clamped.twist_angle() - 0.5).abs() < EPSILON);
+        assert!(clamped.swing_pitchyaw().distance(&dir) < EPSILON);
+    }
+
+    #[test]
+    fn clamp_swing_limits_direction() {
+        let rotation = SwingTwist::from_pitchyaw(PitchYaw::new(1.2, 0.9), -0.3);
+        let limits = PitchYawClamped::new_with_clamps(0., 0., 0.4, 0.6);
+
+        let clamped = rotation.clamp_swing(limits);
+        let swing = clamped.swing_pitchyaw_clamped();
+
+        assert!((swing.y - 0.6).abs() < EPSILON, "Swing was {swing:?}");
+        assert!((swing.p - 0.4).abs() < EPSILON, "Swing was {swing:?}");
+        assert!((clamped.twist_angle() + 0.3).abs() < EPSILON);
+    }
+}
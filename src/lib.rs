@@ -1,14 +1,57 @@
+pub mod controllers;
 pub mod geometric;
+pub mod noise;
 pub mod randomized_values;
 pub mod steppers;
 
 pub mod prelude {
     use super::*;
-    pub use geometric::{pitchyaw::PitchYaw, pitchyawclamped::PitchYawClamped};
-    pub use randomized_values::{RandF32, RandValue, RandVec3};
+    pub use controllers::{
+        first_person::{FirstPersonLook, FirstPersonLookPlugin, LookSmoothing},
+        input::LookInput,
+        look_at::{LookAtJoint, LookAtPlugin, LookAtRig, LookAtTarget},
+        orbit_camera::{OrbitCamera, OrbitCameraPlugin},
+        turret::{Turret, TurretPlugin},
+    };
+    pub use geometric::{
+        angle::Angle,
+        ballistics::{BallisticSolution, BallisticSolutions, Trajectory, solve_launch},
+        cylindrical::Cylindrical,
+        dpitchyaw::DPitchYaw,
+        dpitchyawclamped::DPitchYawClamped,
+        intercept::{InterceptSolution, solve_intercept, solve_intercept_iterative},
+        pitchyaw::PitchYaw,
+        pitchyawclamped::PitchYawClamped,
+        spherical::Spherical,
+        swing_twist::SwingTwist,
+    };
+    pub use noise::{
+        Noise, NoisePoint,
+        drift::{FractalKind, NoiseF32, NoiseKind, NoisePlugin, NoiseSource, NoiseVec3},
+        fractal::{Fbm, Octaves, Ridged},
+        lattice::{PerlinNoise, ValueNoise},
+        simplex::SimplexNoise,
+        worley::WorleyNoise,
+    };
+    pub use randomized_values::{
+        F32Distribution, RandBool, RandColor, RandColorSpace, RandDuration, RandF32, RandI32,
+        RandPitchYaw, RandQuat, RandU32, RandValue, RandVec2, RandVec3, SpreadDistribution,
+        choice::{ChoiceEntry, RandChoice, RandChoicePlugin, WeightedEntry},
+        curves::{RandCurve, RandCurveColor, RandCurveF32, RandCurveVec3},
+        expr::RandExpr,
+        fairness::{PseudoRandomBool, ShuffleBag},
+        poisson::{PoissonDisk2d, PoissonDisk3d, PoissonDomain2d, PoissonDomain3d},
+        rng::{EntityRng, GlobalRng, RngPlugin},
+        shapes::{
+            MeshSurface, PointShape2d, PointShape3d, RandPoint2, RandPoint3, SampleRegion,
+            SplinePath,
+        },
+        spawn::{RandPosition, RandRotation, RandScale, RandTransform, SpawnPattern, Spawner},
+    };
     pub use steppers::{
         core::TickInterpolator,
         linear_stepper::LinearStepper,
+        pose::{PoseRates, PoseSpeed, PoseVelocity},
         spring_stepper::{SPRING_MASS, SpringStepper, critical_damp_coeff},
     };
 }
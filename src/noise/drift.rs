@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{
+    Noise, NoisePoint,
+    fractal::{Fbm, Octaves, Ridged},
+    lattice::{PerlinNoise, ValueNoise},
+    simplex::SimplexNoise,
+    worley::WorleyNoise,
+};
+use crate::randomized_values::RandValue;
+
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoiseKind {
+    Value,
+    Perlin,
+    #[default]
+    Simplex,
+    Worley,
+}
+
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FractalKind {
+    #[default]
+    None,
+    Fbm,
+    Ridged,
+}
+
+/// Noise described as data, e.g. to configure [`NoiseF32`] from an asset file
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoiseSource {
+    pub kind: NoiseKind,
+    pub seed: u32,
+    #[serde(default)]
+    pub fractal: FractalKind,
+    /// Only used with a fractal
+    #[serde(default)]
+    pub octaves: Octaves,
+}
+
+impl NoiseSource {
+    pub fn new(kind: NoiseKind, seed: u32) -> Self {
+        Self {
+            kind,
+            seed,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_fractal(self, fractal: FractalKind, octaves: Octaves) -> Self {
+        Self {
+            fractal,
+            octaves,
+            ..self
+        }
+    }
+}
+
+/// One of the basic noise kinds, picked at runtime
+#[derive(Clone, Copy)]
+struct BaseNoise {
+    kind: NoiseKind,
+    seed: u32,
+}
+
+impl Noise for BaseNoise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        match self.kind {
+            NoiseKind::Value => ValueNoise::new(self.seed).sample(point),
+            NoiseKind::Perlin => PerlinNoise::new(self.seed).sample(point),
+            NoiseKind::Simplex => SimplexNoise::new(self.seed).sample(point),
+            NoiseKind::Worley => WorleyNoise::new(self.seed).sample(point),
+        }
+    }
+}
+
+impl Noise for NoiseSource {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        let source = BaseNoise {
+            kind: self.kind,
+            seed: self.seed,
+        };
+        let octaves = self.octaves;
+
+        match self.fractal {
+            FractalKind::None => source.sample(point),
+            FractalKind::Fbm => Fbm { source, octaves }.sample(point),
+            FractalKind::Ridged => Ridged { source, octaves }.sample(point),
+        }
+    }
+}
+
+/// Noise cells after which the noise position turns back, so that it stays small
+/// enough for `f32` precision however long the time grows
+const MIRROR_PERIOD: f64 = 4096.;
+
+/// Position along the noise for `time` at `frequency`, going back and forth over
+/// `[0, MIRROR_PERIOD]` so that it never jumps
+fn noise_position(time: f64, frequency: f32) -> f32 {
+    let position = (time * frequency as f64).rem_euclid(2. * MIRROR_PERIOD);
+    (MIRROR_PERIOD - (position - MIRROR_PERIOD).abs()) as f32
+}
+
+/// Value drifting smoothly between `min` and `max` over time, driven by noise. Good
+/// for wind strength, idle sway or flickering lights.
+///
+/// Advance it with [`NoiseF32::tick`], or add [`NoisePlugin`] to tick it as a
+/// component; [`RandValue::generate`] gives the value at the current time, without
+/// drawing from the RNG.
+#[derive(Component, Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoiseF32 {
+    pub noise: NoiseSource,
+    /// How fast the value changes, in noise cells per second
+    pub frequency: f32,
+    pub min: f32,
+    pub max: f32,
+    /// Current time, in seconds
+    #[serde(default)]
+    pub time: f64,
+}
+
+impl NoiseF32 {
+    pub fn new(noise: NoiseSource, frequency: f32, min: f32, max: f32) -> Self {
+        Self {
+            noise,
+            frequency,
+            min,
+            max,
+            time: 0.,
+        }
+    }
+
+    /// Starts at a random time, so that instances sharing a seed drift differently
+    pub fn with_random_offset<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+        Self {
+            time: rng.random::<f64>() * 1000.,
+            ..self
+        }
+    }
+
+    pub fn sample(&self, time: f64) -> f32 {
+        let noise = self.noise.sample(noise_position(time, self.frequency));
+        self.min + (noise * 0.5 + 0.5) * (self.max - self.min)
+    }
+
+    pub fn tick(&mut self, dt: Duration) {
+        self.time += dt.as_secs_f64();
+    }
+}
+
+impl RandValue for NoiseF32 {
+    type Out = f32;
+
+    fn generate_with<R: Rng + ?Sized>(&self, _rng: &mut R) -> f32 {
+        self.sample(self.time)
+    }
+
+    fn constant(value: f32) -> Self {
+        Self::new(NoiseSource::default(), 0., value, value)
+    }
+}
+
+/// Value drifting smoothly within `center ± amplitude` on each axis, with the axes
+/// varying independently. Good for camera shake or hovering motion.
+///
+/// Works like [`NoiseF32`].
+#[derive(Component, Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoiseVec3 {
+    pub noise: NoiseSource,
+    /// How fast the offset changes, in noise cells per second
+    pub frequency: f32,
+    #[serde(default)]
+    pub center: Vec3,
+    pub amplitude: Vec3,
+    /// Current time, in seconds
+    #[serde(default)]
+    pub time: f64,
+}
+
+impl NoiseVec3 {
+    pub fn new(noise: NoiseSource, frequency: f32, amplitude: Vec3) -> Self {
+        Self {
+            noise,
+            frequency,
+            center: Vec3::ZERO,
+            amplitude,
+            time: 0.,
+        }
+    }
+
+    /// Starts at a random time, so that instances sharing a seed drift differently
+    pub fn with_random_offset<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+        Self {
+            time: rng.random::<f64>() * 1000.,
+            ..self
+        }
+    }
+
+    pub fn sample(&self, time: f64) -> Vec3 {
+        let t = noise_position(time, self.frequency);
+        // Rows of 2D noise far apart from each other, one per axis
+        let axis = |row: f32| self.noise.sample(Vec2::new(t, row * 31.7));
+        self.center + Vec3::new(axis(0.), axis(1.), axis(2.)) * self.amplitude
+    }
+
+    pub fn tick(&mut self, dt: Duration) {
+        self.time += dt.as_secs_f64();
+    }
+}
+
+impl RandValue for NoiseVec3 {
+    type Out = Vec3;
+
+    fn generate_with<R: Rng + ?Sized>(&self, _rng: &mut R) -> Vec3 {
+        self.sample(self.time)
+    }
+
+    fn constant(value: Vec3) -> Self {
+        Self {
+            center: value,
+            ..Self::new(NoiseSource::default(), 0., Vec3::ZERO)
+        }
+    }
+}
+
+/// Advances every [`NoiseF32`] and [`NoiseVec3`] component
+pub fn tick_noise(
+    time: Res<Time>,
+    mut values: Query<&mut NoiseF32>,
+    mut vectors: Query<&mut NoiseVec3>,
+) {
+    for mut value in &mut values {
+        value.tick(time.delta());
+    }
+    for mut vector in &mut vectors {
+        vector.tick(time.delta());
+    }
+}
+
+/// Adds the noise tick system. It runs in `PreUpdate`, so that `Update` systems see
+/// the values for the current frame.
+pub struct NoisePlugin;
+
+impl Plugin for NoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, tick_noise);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drifts_smoothly_within_range() {
+        let mut value = NoiseF32::new(
+            NoiseSource::new(NoiseKind::Simplex, 3).with_fractal(FractalKind::Fbm, Octaves::new(3)),
+            2.,
+            10.,
+            20.,
+        );
+
+        let mut previous = value.generate();
+        for _ in 0..1000 {
+            value.tick(Duration::from_millis(2));
+            let current = value.generate();
+            assert!((10.0..=20.).contains(&current));
+            assert!((current - previous).abs() < 1., "{previous} -> {current}");
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn axes_are_independent() {
+        let shake = NoiseVec3::new(NoiseSource::new(NoiseKind::Perlin, 1), 1., Vec3::ONE);
+        let samples: Vec<Vec3> = (0..200).map(|i| shake.sample(i as f64 * 0.37)).collect();
+
+        let correlation = samples.iter().map(|s| s.x * s.y).sum::<f32>()
+            / (samples.iter().map(|s| s.x * s.x).sum::<f32>()
+                * samples.iter().map(|s| s.y * s.y).sum::<f32>())
+            .sqrt();
+        assert!(correlation.abs() < 0.5, "{correlation}");
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(NoiseF32::constant(4.).generate(), 4.);
+        assert_eq!(
+            NoiseVec3::constant(Vec3::new(1., -2., 0.)).generate(),
+            Vec3::new(1., -2., 0.)
+        );
+    }
+
+    #[test]
+    fn stays_smooth_after_a_long_time() {
+        let value = NoiseF32::new(NoiseSource::new(NoiseKind::Perlin, 5), 3., -1., 1.);
+        let start = 1e7;
+
+        let mut previous = value.sample(start);
+        for i in 1..1000 {
+            let current = value.sample(start + i as f64 * 0.002);
+            assert!((current - previous).abs() < 0.1, "{previous} -> {current}");
+            previous = current;
+        }
+        // Turning back at the end of the mirror period does not jump either
+        let turn = MIRROR_PERIOD / 3.;
+        assert!((value.sample(turn - 1e-4) - value.sample(turn + 1e-4)).abs() < 0.01);
+    }
+
+    #[test]
+    fn plugin_ticks_components() {
+        let mut app = App::new();
+        app.init_resource::<Time>().add_plugins(NoisePlugin);
+        let entity = app
+            .world_mut()
+            .spawn(NoiseF32::new(NoiseSource::default(), 1., 0., 1.))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(250));
+        app.update();
+
+        assert_eq!(app.world().get::<NoiseF32>(entity).unwrap().time, 0.25);
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::{math::Vec4, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+use super::{Noise, NoisePoint};
+
+/// Octave settings shared by the fractal combinators
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Serialize, Deserialize)]
+pub struct Octaves {
+    pub count: u32,
+    /// Frequency multiplier between octaves
+    pub lacunarity: f32,
+    /// Amplitude multiplier between octaves
+    pub gain: f32,
+}
+
+impl Default for Octaves {
+    fn default() -> Self {
+        Self {
+            count: 4,
+            lacunarity: 2.,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Octaves {
+    pub fn new(count: u32) -> Self {
+        Self {
+            count,
+            ..Self::default()
+        }
+    }
+
+    /// Weighted average of `octave(value)` over the octaves, each sampled at a higher
+    /// frequency and offset to decorrelate them
+    fn accumulate<P: NoisePoint>(&self, point: P, octave: impl Fn(P) -> f32) -> f32 {
+        let point = point.to_vec4();
+        let mut frequency = 1.;
+        let mut amplitude = 1.;
+        let mut total = 0.;
+        let mut total_amplitude = 0.;
+
+        for index in 0..self.count.max(1) {
+            let offset = Vec4::splat(index as f32 * 17.31);
+            total += amplitude * octave(P::from_vec4(point * frequency + offset));
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        total / total_amplitude
+    }
+}
+
+/// Fractional Brownian motion: sums octaves of `source` at increasing frequencies and
+/// decreasing amplitudes, adding finer detail
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fbm<N> {
+    pub source: N,
+    pub octaves: Octaves,
+}
+
+impl<N> Fbm<N> {
+    pub fn new(source: N, octaves: u32) -> Self {
+        Self {
+            source,
+            octaves: Octaves::new(octaves),
+        }
+    }
+}
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        self.octaves.accumulate(point, |p| self.source.sample(p))
+    }
+}
+
+/// Like [`Fbm`], but folds each octave around zero, giving sharp ridges, e.g. for
+/// mountain ranges or lightning
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ridged<N> {
+    pub source: N,
+    pub octaves: Octaves,
+}
+
+impl<N> Ridged<N> {
+    pub fn new(source: N, octaves: u32) -> Self {
+        Self {
+            source,
+            octaves: Octaves::new(octaves),
+        }
+    }
+}
+
+impl<N: Noise> Noise for Ridged<N> {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        let ridges = self.octaves.accumulate(point, |p| {
+            let ridge = 1. - self.source.sample(p).abs();
+            ridge * ridge
+        });
+
+        ridges * 2. - 1.
+    }
+}
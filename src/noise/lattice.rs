@@ -0,0 +1,86 @@
+use bevy::{math::Vec4, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+use super::{Noise, NoisePoint, gradient, hash_unit};
+
+/// Random values at integer points, smoothly interpolated in between. Cheap, but
+/// blockier than gradient noise.
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueNoise {
+    pub seed: u32,
+}
+
+/// Classic Perlin gradient noise
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerlinNoise {
+    pub seed: u32,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl PerlinNoise {
+    pub fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl Noise for ValueNoise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        interpolate_lattice(point.to_vec4(), P::DIMENSIONS, |cell, _| {
+            hash_unit(self.seed, cell)
+        })
+    }
+}
+
+impl Noise for PerlinNoise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        let dimensions = P::DIMENSIONS;
+        let value = interpolate_lattice(point.to_vec4(), dimensions, |cell, offset| {
+            gradient(self.seed, cell, dimensions).dot(offset)
+        });
+
+        // Unit gradients reach at most sqrt(dimensions) / 2
+        (value * 2. / (dimensions as f32).sqrt()).clamp(-1., 1.)
+    }
+}
+
+/// Quintic fade, with zero first and second derivatives at 0 and 1
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+/// Blends `corner(cell, offset)` over the corners of the lattice cell containing
+/// `point`, where `offset` goes from the corner to `point`
+fn interpolate_lattice(
+    point: Vec4,
+    dimensions: usize,
+    corner: impl Fn([i32; 4], Vec4) -> f32,
+) -> f32 {
+    let base = point.floor();
+    let local = point - base;
+    let base = base.as_ivec4();
+    let weights = Vec4::new(fade(local.x), fade(local.y), fade(local.z), fade(local.w));
+
+    let mut value = 0.;
+    for corner_bits in 0..(1 << dimensions) {
+        let mut cell = base.to_array();
+        let mut offset = local;
+        let mut weight = 1.;
+        for axis in 0..dimensions {
+            if corner_bits & (1 << axis) != 0 {
+                cell[axis] += 1;
+                offset[axis] -= 1.;
+                weight *= weights[axis];
+            } else {
+                weight *= 1. - weights[axis];
+            }
+        }
+        value += weight * corner(cell, offset);
+    }
+
+    value
+}
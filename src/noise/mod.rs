@@ -0,0 +1,184 @@
+use bevy::math::{Vec2, Vec3, Vec4};
+
+pub mod drift;
+pub mod fractal;
+pub mod lattice;
+pub mod simplex;
+pub mod worley;
+
+/// Coherent noise: values that change smoothly with the sampled point, in `[-1, 1]`.
+///
+/// Implemented for 1D to 4D points (`f32`, `Vec2`, `Vec3`, `Vec4`). Noise with the
+/// same seed always gives the same values.
+pub trait Noise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32;
+}
+
+/// Points noise can be sampled at, from 1D to 4D
+pub trait NoisePoint: Copy {
+    const DIMENSIONS: usize;
+
+    /// The point's coordinates, padded with zeros
+    fn to_vec4(self) -> Vec4;
+    fn from_vec4(vec: Vec4) -> Self;
+}
+
+impl NoisePoint for f32 {
+    const DIMENSIONS: usize = 1;
+
+    fn to_vec4(self) -> Vec4 {
+        Vec4::new(self, 0., 0., 0.)
+    }
+
+    fn from_vec4(vec: Vec4) -> Self {
+        vec.x
+    }
+}
+
+impl NoisePoint for Vec2 {
+    const DIMENSIONS: usize = 2;
+
+    fn to_vec4(self) -> Vec4 {
+        self.extend(0.).extend(0.)
+    }
+
+    fn from_vec4(vec: Vec4) -> Self {
+        vec.truncate().truncate()
+    }
+}
+
+impl NoisePoint for Vec3 {
+    const DIMENSIONS: usize = 3;
+
+    fn to_vec4(self) -> Vec4 {
+        self.extend(0.)
+    }
+
+    fn from_vec4(vec: Vec4) -> Self {
+        vec.truncate()
+    }
+}
+
+impl NoisePoint for Vec4 {
+    const DIMENSIONS: usize = 4;
+
+    fn to_vec4(self) -> Vec4 {
+        self
+    }
+
+    fn from_vec4(vec: Vec4) -> Self {
+        vec
+    }
+}
+
+/// Pseudo-random 32 bit hash of a lattice point
+pub(crate) fn hash(seed: u32, cell: [i32; 4]) -> u32 {
+    let mut hash = seed.wrapping_mul(0x9E37_79B9) ^ 0x85EB_CA6B;
+    for coordinate in cell {
+        hash ^= (coordinate as u32).wrapping_mul(0xC2B2_AE35);
+        hash = hash.rotate_left(13).wrapping_mul(0x27D4_EB2F);
+        hash ^= hash >> 15;
+    }
+    hash = hash.wrapping_mul(0x1656_67B1);
+    hash ^ (hash >> 16)
+}
+
+/// Hash mapped to `[-1, 1]`
+pub(crate) fn hash_unit(seed: u32, cell: [i32; 4]) -> f32 {
+    hash(seed, cell) as f32 / u32::MAX as f32 * 2. - 1.
+}
+
+/// Pseudo-random unit vector in the first `dimensions` coordinates
+pub(crate) fn gradient(seed: u32, cell: [i32; 4], dimensions: usize) -> Vec4 {
+    let mut gradient = Vec4::ZERO;
+    for (axis, component) in gradient.as_mut().iter_mut().enumerate().take(dimensions) {
+        *component = hash_unit(
+            seed.wrapping_add((axis as u32).wrapping_mul(0x68E3_1DA4)),
+            cell,
+        );
+    }
+    gradient.normalize_or(Vec4::X)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fractal::{Fbm, Ridged},
+        lattice::{PerlinNoise, ValueNoise},
+        simplex::SimplexNoise,
+        worley::WorleyNoise,
+        *,
+    };
+
+    /// Checks the range and smoothness of a noise over a line through `P` space
+    fn check<N: Noise, P: NoisePoint>(noise: &N, direction: Vec4) {
+        let step = 0.001;
+        let mut previous = noise.sample(P::from_vec4(Vec4::ZERO));
+        let mut min = f32::INFINITY;
+        let mut max = f32::NEG_INFINITY;
+
+        for i in 1..20000 {
+            let value = noise.sample(P::from_vec4(direction * (i as f32 * step)));
+            assert!((-1.0..=1.).contains(&value), "{value} out of range");
+            assert!(
+                (value - previous).abs() < 0.05,
+                "Jumped from {previous} to {value}"
+            );
+            previous = value;
+            min = min.min(value);
+            max = max.max(value);
+        }
+        assert!(
+            max - min > 0.4,
+            "Too flat: {min}..{max} in {}D",
+            P::DIMENSIONS
+        );
+    }
+
+    fn check_all_dimensions<N: Noise>(noise: &N) {
+        check::<N, f32>(noise, Vec4::X);
+        check::<N, Vec2>(noise, Vec4::new(0.8, 0.6, 0., 0.));
+        check::<N, Vec3>(noise, Vec4::new(0.48, 0.6, 0.64, 0.));
+        check::<N, Vec4>(noise, Vec4::new(0.4, 0.5, 0.5, 0.58).normalize());
+    }
+
+    #[test]
+    fn value_noise() {
+        check_all_dimensions(&ValueNoise::new(1));
+    }
+
+    #[test]
+    fn perlin_noise() {
+        check_all_dimensions(&PerlinNoise::new(2));
+    }
+
+    #[test]
+    fn simplex_noise() {
+        check_all_dimensions(&SimplexNoise::new(3));
+    }
+
+    #[test]
+    fn worley_noise() {
+        check_all_dimensions(&WorleyNoise::new(4));
+    }
+
+    #[test]
+    fn fractals() {
+        check_all_dimensions(&Fbm::new(PerlinNoise::new(5), 4));
+        check_all_dimensions(&Ridged::new(SimplexNoise::new(6), 3));
+    }
+
+    #[test]
+    fn seeds_change_values() {
+        let point = Vec3::new(0.3, 1.7, -2.2);
+
+        assert_eq!(
+            PerlinNoise::new(7).sample(point),
+            PerlinNoise::new(7).sample(point)
+        );
+        assert_ne!(
+            PerlinNoise::new(7).sample(point),
+            PerlinNoise::new(8).sample(point)
+        );
+    }
+}
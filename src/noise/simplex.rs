@@ -0,0 +1,70 @@
+use bevy::{math::Vec4, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+use super::{Noise, NoisePoint, gradient};
+
+/// Simplex gradient noise. Smoother than Perlin noise, without its axis-aligned
+/// artifacts, and cheaper in higher dimensions.
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimplexNoise {
+    pub seed: u32,
+}
+
+impl SimplexNoise {
+    pub fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+/// Squared radius of each vertex's contribution
+const RADIUS_SQUARED: f32 = 0.5;
+
+/// Brings the output of each dimension close to `[-1, 1]`
+const SCALE: [f32; 4] = [70., 99., 108., 108.];
+
+impl Noise for SimplexNoise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        let dimensions = P::DIMENSIONS;
+        let n = dimensions as f32;
+        let point = point.to_vec4();
+
+        // Skew the space so that simplices become the halves of hypercubes
+        let skew = ((n + 1.).sqrt() - 1.) / n;
+        let unskew = (1. - 1. / (n + 1.).sqrt()) / n;
+
+        let sum: f32 = point.to_array()[..dimensions].iter().sum();
+        let base = (point + Vec4::splat(sum * skew)).floor();
+        let base_sum: f32 = base.to_array()[..dimensions].iter().sum();
+        let mut offset = point - (base - Vec4::splat(base_sum * unskew));
+        let mut cell = base.as_ivec4().to_array();
+        for axis in dimensions..4 {
+            offset[axis] = 0.;
+            cell[axis] = 0;
+        }
+
+        // Walk the simplex's vertices, stepping along the axes with the largest
+        // offsets first
+        let mut order = [0, 1, 2, 3];
+        order[..dimensions].sort_by(|a, b| offset[*b].total_cmp(&offset[*a]));
+
+        let mut value = 0.;
+        for vertex in 0..=dimensions {
+            if vertex > 0 {
+                let axis = order[vertex - 1];
+                cell[axis] += 1;
+                offset[axis] -= 1.;
+                for other in offset.as_mut().iter_mut().take(dimensions) {
+                    *other += unskew;
+                }
+            }
+
+            let falloff = RADIUS_SQUARED - offset.length_squared();
+            if falloff > 0. {
+                let falloff = falloff * falloff;
+                value += falloff * falloff * gradient(self.seed, cell, dimensions).dot(offset);
+            }
+        }
+
+        (value * SCALE[dimensions - 1]).clamp(-1., 1.)
+    }
+}
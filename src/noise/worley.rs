@@ -0,0 +1,55 @@
+use bevy::{math::Vec4, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+use super::{Noise, NoisePoint, hash_unit};
+
+/// Cellular (Worley) noise: distance to the nearest of a set of random feature
+/// points, one per unit cell. Gives -1 on the feature points, growing towards 1 far
+/// from them.
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorleyNoise {
+    pub seed: u32,
+}
+
+impl WorleyNoise {
+    pub fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    /// Distance to the nearest feature point
+    pub fn distance<P: NoisePoint>(&self, point: P) -> f32 {
+        let dimensions = P::DIMENSIONS;
+        let point = point.to_vec4();
+        let base = point.floor().as_ivec4().to_array();
+
+        let mut nearest = f32::INFINITY;
+        for neighbour in 0..3usize.pow(dimensions as u32) {
+            let mut cell = base;
+            let mut remaining = neighbour;
+            for coordinate in cell.iter_mut().take(dimensions) {
+                *coordinate += (remaining % 3) as i32 - 1;
+                remaining /= 3;
+            }
+
+            let mut feature = Vec4::ZERO;
+            for axis in 0..dimensions {
+                let jitter = hash_unit(
+                    self.seed
+                        .wrapping_add((axis as u32).wrapping_mul(0x3C6E_F372)),
+                    cell,
+                ) * 0.5
+                    + 0.5;
+                feature[axis] = cell[axis] as f32 + jitter;
+            }
+            nearest = nearest.min(feature.distance_squared(point));
+        }
+
+        nearest.sqrt()
+    }
+}
+
+impl Noise for WorleyNoise {
+    fn sample<P: NoisePoint>(&self, point: P) -> f32 {
+        (self.distance(point) * 2. - 1.).clamp(-1., 1.)
+    }
+}
@@ -0,0 +1,355 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{fmt, marker::PhantomData};
+
+use super::RandValue;
+
+/// Weighted random table, e.g. for loot drops or spawn lists.
+///
+/// Generating a value rolls the table and returns everything it dropped: all the
+/// `guaranteed` entries, then `picks` rolls among the weighted `entries`. Entries can
+/// be nested tables, which are rolled in turn.
+///
+/// Deserializes from RON, and can be loaded as an asset with [`RandChoicePlugin`]:
+///
+/// ```ron
+/// (
+///     guaranteed: [Item("gold")],
+///     entries: [
+///         (weight: 10.0, entry: Item("sword")),
+///         (weight: 5.0, entry: Nothing),
+///         (entry: Table((entries: [(entry: Item("ruby")), (entry: Item("emerald"))]))),
+///     ],
+///     picks: 2,
+///     unique: true,
+/// )
+/// ```
+///
+/// Simple tables can also be written as an [expression](super::expr), e.g.
+/// `"choice(sword: 10, _: 5, choice(ruby, emerald))"`.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(remote = "Self", bound(deserialize = "T: DeserializeOwned"))]
+pub struct RandChoice<T: Send + Sync + TypePath> {
+    /// Always dropped, on top of the weighted picks
+    #[serde(default = "Vec::new")]
+    pub guaranteed: Vec<ChoiceEntry<T>>,
+    #[serde(default = "Vec::new")]
+    pub entries: Vec<WeightedEntry<T>>,
+    /// How many times to roll among `entries`
+    #[serde(default = "default_picks")]
+    pub picks: u32,
+    /// Pick without replacement, so that no entry is rolled twice
+    #[serde(default)]
+    pub unique: bool,
+}
+
+fn default_picks() -> u32 {
+    1
+}
+
+fn default_weight() -> f32 {
+    1.
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct WeightedEntry<T: Send + Sync + TypePath> {
+    /// Relative chance of this entry being picked. Non-positive weights are never picked
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    pub entry: ChoiceEntry<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub enum ChoiceEntry<T: Send + Sync + TypePath> {
+    Item(T),
+    /// Rolled in turn, adding everything it drops
+    Table(RandChoice<T>),
+    /// Drops nothing, to give a chance of an empty roll
+    Nothing,
+}
+
+impl<T: Send + Sync + TypePath> Default for RandChoice<T> {
+    fn default() -> Self {
+        Self {
+            guaranteed: Vec::new(),
+            entries: Vec::new(),
+            picks: default_picks(),
+            unique: false,
+        }
+    }
+}
+
+impl<T: Send + Sync + TypePath> RandChoice<T> {
+    /// Table picking one of `entries`, each given as `(weight, item)`
+    pub fn weighted(entries: impl IntoIterator<Item = (f32, T)>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(weight, item)| WeightedEntry::new(weight, ChoiceEntry::Item(item)))
+                .collect(),
+            ..default()
+        }
+    }
+
+    pub fn with_picks(self, picks: u32, unique: bool) -> Self {
+        Self {
+            picks,
+            unique,
+            ..self
+        }
+    }
+
+    pub fn with_guaranteed(mut self, entry: ChoiceEntry<T>) -> Self {
+        self.guaranteed.push(entry);
+        self
+    }
+
+    pub fn with_entry(mut self, weight: f32, entry: ChoiceEntry<T>) -> Self {
+        self.entries.push(WeightedEntry::new(weight, entry));
+        self
+    }
+}
+
+impl<T: Send + Sync + TypePath> WeightedEntry<T> {
+    pub fn new(weight: f32, entry: ChoiceEntry<T>) -> Self {
+        Self { weight, entry }
+    }
+}
+
+impl<T: Clone + Send + Sync + TypePath> ChoiceEntry<T> {
+    fn roll_into<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut Vec<T>) {
+        match self {
+            ChoiceEntry::Item(item) => out.push(item.clone()),
+            ChoiceEntry::Table(table) => table.roll_into(rng, out),
+            ChoiceEntry::Nothing => {}
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + TypePath> RandChoice<T> {
+    fn roll_into<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut Vec<T>) {
+        for entry in &self.guaranteed {
+            entry.roll_into(rng, out);
+        }
+
+        let mut weights: Vec<f32> = self.entries.iter().map(|e| e.weight.max(0.)).collect();
+        for _ in 0..self.picks {
+            let Some(index) = weighted_index(rng, &weights) else {
+                break;
+            };
+            self.entries[index].entry.roll_into(rng, out);
+            if self.unique {
+                weights[index] = 0.;
+            }
+        }
+    }
+}
+
+/// Index picked with probability proportional to its weight, or `None` if all
+/// weights are zero
+fn weighted_index<R: Rng + ?Sized>(rng: &mut R, weights: &[f32]) -> Option<usize> {
+    let total: f32 = weights.iter().sum();
+    if total <= 0. {
+        return None;
+    }
+
+    let mut remaining = rng.random::<f32>() * total;
+    let last = weights.iter().rposition(|w| *w > 0.)?;
+    for (index, weight) in weights.iter().enumerate().take(last) {
+        if remaining < *weight {
+            return Some(index);
+        }
+        remaining -= weight;
+    }
+    Some(last)
+}
+
+impl<T: Clone + Send + Sync + TypePath> RandValue for RandChoice<T> {
+    /// Everything dropped by a single roll of the table
+    type Out = Vec<T>;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        let mut out = Vec::new();
+        self.roll_into(rng, &mut out);
+        out
+    }
+
+    /// Table that always drops exactly `value`
+    fn constant(value: Vec<T>) -> Self {
+        Self {
+            guaranteed: value.into_iter().map(ChoiceEntry::Item).collect(),
+            picks: 0,
+            ..default()
+        }
+    }
+}
+
+/// Loads [`RandChoice`] assets from RON files
+#[derive(TypePath)]
+pub struct RandChoiceLoader<T> {
+    extensions: Vec<&'static str>,
+    marker: PhantomData<fn() -> T>,
+}
+
+#[derive(Debug)]
+pub enum RandChoiceLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for RandChoiceLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RandChoiceLoaderError::Io(err) => write!(f, "could not read choice table: {err}"),
+            RandChoiceLoaderError::Ron(err) => write!(f, "could not parse choice table: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RandChoiceLoaderError {}
+
+impl From<std::io::Error> for RandChoiceLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for RandChoiceLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+impl<T> AssetLoader for RandChoiceLoader<T>
+where
+    T: DeserializeOwned + Send + Sync + TypePath,
+{
+    type Asset = RandChoice<T>;
+    type Settings = ();
+    type Error = RandChoiceLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
+
+/// Registers `RandChoice<T>` as an asset, loaded from RON files with the given
+/// extensions (without the leading dot).
+///
+/// Add one plugin per item type. When several item types share an extension, the
+/// loader is picked from the type of the requested handle.
+pub struct RandChoicePlugin<T> {
+    pub extensions: Vec<&'static str>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RandChoicePlugin<T> {
+    pub fn new(extensions: &[&'static str]) -> Self {
+        Self {
+            extensions: extensions.to_vec(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Plugin for RandChoicePlugin<T>
+where
+    T: DeserializeOwned + Send + Sync + TypePath,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<RandChoice<T>>()
+            .register_asset_loader(RandChoiceLoader::<T> {
+                extensions: self.extensions.clone(),
+                marker: PhantomData,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::rng::GlobalRng;
+
+    const SAMPLES: usize = 20000;
+
+    #[test]
+    fn picks_follow_weights() {
+        let table = RandChoice::weighted([(3., "common"), (1., "rare")]);
+        let mut rng = GlobalRng::from_seed(11);
+
+        let rare = (0..SAMPLES)
+            .filter(|_| table.generate_with(&mut rng) == ["rare"])
+            .count();
+        assert!((rare as f32 / SAMPLES as f32 - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn unique_picks_never_repeat() {
+        let table = RandChoice::weighted([(1., 'a'), (100., 'b'), (1., 'c')]).with_picks(3, true);
+        let mut rng = GlobalRng::from_seed(11);
+
+        for _ in 0..100 {
+            let mut drops = table.generate_with(&mut rng);
+            drops.sort();
+            assert_eq!(drops, ['a', 'b', 'c']);
+        }
+    }
+
+    #[test]
+    fn guaranteed_nested_and_nothing() {
+        let gems = RandChoice::weighted([(1., "ruby"), (1., "emerald")]);
+        let table = RandChoice::default()
+            .with_guaranteed(ChoiceEntry::Item("gold"))
+            .with_entry(1., ChoiceEntry::Table(gems))
+            .with_entry(1., ChoiceEntry::Nothing);
+        let mut rng = GlobalRng::from_seed(11);
+
+        let mut saw_nothing = false;
+        let mut saw_gem = false;
+        for _ in 0..100 {
+            let drops = table.generate_with(&mut rng);
+            assert_eq!(drops[0], "gold");
+            match drops.len() {
+                1 => saw_nothing = true,
+                2 => saw_gem |= ["ruby", "emerald"].contains(&drops[1]),
+                _ => panic!("Unexpected drops {drops:?}"),
+            }
+        }
+        assert!(saw_nothing && saw_gem);
+    }
+
+    #[test]
+    fn deserializes_from_ron() {
+        let table: RandChoice<String> = ron::from_str(
+            r#"(
+                guaranteed: [Item("gold")],
+                entries: [
+                    (weight: 0.0, entry: Item("sword")),
+                    (entry: Table((entries: [(entry: Item("ruby"))]))),
+                ],
+                picks: 2,
+            )"#,
+        )
+        .unwrap();
+        let mut rng = GlobalRng::from_seed(11);
+
+        assert_eq!(table.generate_with(&mut rng), ["gold", "ruby", "ruby"]);
+    }
+}
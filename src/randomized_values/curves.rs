@@ -0,0 +1,134 @@
+use bevy::{
+    color::LinearRgba,
+    math::{
+        StableInterpolate, Vec3,
+        curve::{Curve, UnevenSampleAutoCurve, cores::UnevenCoreError},
+    },
+    reflect::Reflect,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Random value between two curves, e.g. a particle's size over its lifetime. Each
+/// instance picks a blend factor once (see [`RandCurve::factor_with`]), then follows
+/// the blend of `min` and `max` for its whole life.
+///
+/// Both curves are evaluated at a normalized time: `0` is the start of each curve's
+/// domain and `1` its end, so they don't need to share keyframe times.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RandCurve<T> {
+    pub min: UnevenSampleAutoCurve<T>,
+    pub max: UnevenSampleAutoCurve<T>,
+}
+
+pub type RandCurveF32 = RandCurve<f32>;
+pub type RandCurveVec3 = RandCurve<Vec3>;
+/// Colors are blended in linear space, as shaders expect them
+pub type RandCurveColor = RandCurve<LinearRgba>;
+
+impl<T: StableInterpolate> RandCurve<T> {
+    pub fn new(min: UnevenSampleAutoCurve<T>, max: UnevenSampleAutoCurve<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Builds both curves from `(time, value)` keyframes
+    pub fn from_keyframes(
+        min: impl IntoIterator<Item = (f32, T)>,
+        max: impl IntoIterator<Item = (f32, T)>,
+    ) -> Result<Self, UnevenCoreError> {
+        Ok(Self::new(
+            UnevenSampleAutoCurve::new(min)?,
+            UnevenSampleAutoCurve::new(max)?,
+        ))
+    }
+
+    /// Follows `curve` exactly
+    pub fn from_curve(curve: UnevenSampleAutoCurve<T>) -> Self {
+        Self::new(curve.clone(), curve)
+    }
+
+    /// Constant random value between `min` and `max`, the same over time
+    pub fn between(min: T, max: T) -> Self {
+        let flat = |value: T| {
+            UnevenSampleAutoCurve::new([(0., value.clone()), (1., value)])
+                .expect("Two distinct keyframe times")
+        };
+        Self::new(flat(min), flat(max))
+    }
+
+    /// Picks an instance's blend factor between `min` (0) and `max` (1). Store it
+    /// with the instance and pass it to [`RandCurve::evaluate`].
+    pub fn factor_with<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        rng.random()
+    }
+
+    /// Picks a blend factor using the thread RNG
+    pub fn factor(&self) -> f32 {
+        self.factor_with(&mut rand::rng())
+    }
+
+    /// Value of the instance with blend `factor` at normalized time `t`, clamped to
+    /// `[0, 1]`
+    pub fn evaluate(&self, factor: f32, t: f32) -> T {
+        let min = sample_normalized(&self.min, t);
+        let max = sample_normalized(&self.max, t);
+        min.interpolate_stable(&max, factor.clamp(0., 1.))
+    }
+}
+
+fn sample_normalized<T: StableInterpolate>(curve: &UnevenSampleAutoCurve<T>, t: f32) -> T {
+    let domain = curve.domain();
+    let time = domain.start() + t.clamp(0., 1.) * domain.length();
+    curve.sample_clamped(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blends_between_curves() {
+        let curve =
+            RandCurveF32::from_keyframes([(0., 0.), (1., 10.)], [(0., 10.), (1., 30.)]).unwrap();
+
+        assert_eq!(curve.evaluate(0., 0.5), 5.);
+        assert_eq!(curve.evaluate(1., 0.5), 20.);
+        assert_eq!(curve.evaluate(0.5, 0.5), 12.5);
+        assert_eq!(curve.evaluate(0.5, 2.), 20.);
+    }
+
+    #[test]
+    fn normalizes_each_domain() {
+        let curve = RandCurveVec3::from_keyframes(
+            [(0., Vec3::ZERO), (4., Vec3::splat(4.))],
+            [(-1., Vec3::ZERO), (0., Vec3::ZERO), (1., Vec3::ONE)],
+        )
+        .unwrap();
+
+        assert_eq!(curve.evaluate(0., 0.25), Vec3::ONE);
+        assert_eq!(curve.evaluate(1., 0.75), Vec3::splat(0.5));
+    }
+
+    #[test]
+    fn factor_stays_fixed_per_instance() {
+        let curve = RandCurveColor::between(LinearRgba::BLACK, LinearRgba::WHITE);
+        let factor = curve.factor();
+
+        assert!((0.0..1.).contains(&factor));
+        assert_eq!(curve.evaluate(factor, 0.), curve.evaluate(factor, 1.));
+        assert!((curve.evaluate(factor, 0.3).red - factor).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ron_roundtrip() {
+        let curve =
+            RandCurveF32::from_keyframes([(0., 1.), (1., 2.)], [(0., 3.), (0.5, 5.), (1., 4.)])
+                .unwrap();
+        let text = ron::to_string(&curve).unwrap();
+        let loaded: RandCurveF32 = ron::from_str(&text).unwrap();
+
+        for t in [0., 0.25, 0.6, 1.] {
+            assert_eq!(loaded.evaluate(0.3, t), curve.evaluate(0.3, t));
+        }
+    }
+}
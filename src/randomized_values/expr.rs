@@ -0,0 +1,1229 @@
+//! Compact string syntax for random values, for data files written by hand.
+//!
+//! Where a random value is expected, RON and JSON files can give either the full
+//! struct or one of these expressions:
+//!
+//! | Type | Examples |
+//! | ---- | -------- |
+//! | [`RandF32`] | `"2.5"`, `"3..7"`, `"5 ± 1.5"` (or `+-`), `"normal(10, 2)"`, `"normal(4..16, 2)"`, `"triangular(0..10, 2)"`, `"exponential(0..10, 0.5)"`, `"log_uniform(20..2000)"` |
+//! | [`RandI32`], [`RandU32`] | `"4"`, `"3..=7"`, `"3..8"` (end excluded), `"5 ± 2"` |
+//! | [`RandBool`] | `"true"`, `"25%"`, `"0.25"` |
+//! | [`RandDuration`] | `"2s"`, `"1.5s..3s"`, `"500ms ± 100ms"`, `"1min"` |
+//! | [`RandVec2`], [`RandVec3`] | `"(0, 1, 0)"`, `"2..5 * (0, 1, 0)"`, `"3 * (0, 1, 0) spread 30deg solid_angle"` |
+//! | [`RandChoice`] | `"choice(a: 3, b: 1)"`, `"choice(Sword, Shield: 2, _: 5, choice(Ruby, Emerald))"` |
+//!
+//! `normal(mean, std_dev)` clamps to three standard deviations around the mean.
+//! Angles are in radians unless followed by `deg` or `°`. In choices, a missing weight
+//! is 1, `_` drops nothing and items are read as RON values, or as plain strings.
+//!
+//! Values serialize back to their shortest expression, or to the full struct when
+//! there is none (e.g. for [`F32Distribution::Curve`]).
+
+use bevy::{
+    math::{Vec2, Vec3},
+    reflect::TypePath,
+};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeOwned, IntoDeserializer, MapAccess, Visitor},
+};
+use std::{fmt, marker::PhantomData, str::FromStr, time::Duration};
+
+use super::{
+    F32Distribution, RandBool, RandDuration, RandF32, RandI32, RandU32, RandValue, RandVec2,
+    RandVec3, SpreadDistribution,
+    choice::{ChoiceEntry, RandChoice, WeightedEntry},
+};
+
+/// Random values with a compact string form, see the [module docs](self)
+pub trait RandExpr: Sized {
+    fn parse_expr(input: &str) -> Result<Self, ParseError>;
+    /// Shortest expression for this value, if it has one
+    fn to_expr(&self) -> Option<String>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Character offset of the error in the expression
+    pub position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Deserialization half of [`RandExpr`], with the full struct form as a fallback
+pub(crate) trait ExprDeserialize: Sized {
+    fn parse(input: &str) -> Result<Self, ParseError>;
+    fn deserialize_full<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+struct ExprVisitor<T>(PhantomData<T>);
+
+impl<T: ExprDeserialize> ExprVisitor<T> {
+    fn parse<E: de::Error>(&self, input: &str) -> Result<T, E> {
+        T::parse(input).map_err(|err| E::custom(format!("invalid expression `{input}`: {err}")))
+    }
+}
+
+impl<'de, T: ExprDeserialize> Visitor<'de> for ExprVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an expression string or a struct")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        self.parse(value)
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<T, E> {
+        self.parse(&value.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        self.parse(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        self.parse(&value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> {
+        self.parse(&value.to_string())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<T, A::Error> {
+        T::deserialize_full(de::value::MapAccessDeserializer::new(map))
+    }
+}
+
+/// Accepts an expression or the full struct from human-readable formats. Binary
+/// formats can't tell them apart without `deserialize_any`, so they only use the full
+/// struct, which is also what serialization writes to them
+fn deserialize_expr<'de, T: ExprDeserialize, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(ExprVisitor(PhantomData))
+    } else {
+        T::deserialize_full(deserializer)
+    }
+}
+
+/// Implements serde and [`FromStr`] through [`RandExpr`] for types deriving serde with
+/// `#[serde(remote = "Self")]`, which keeps the derived full form as inherent
+/// functions
+macro_rules! expr_serde {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.to_expr().filter(|_| serializer.is_human_readable()) {
+                    Some(expr) => serializer.serialize_str(&expr),
+                    None => <$ty>::serialize(self, serializer),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_expr(deserializer)
+            }
+        }
+
+        impl ExprDeserialize for $ty {
+            fn parse(input: &str) -> Result<Self, ParseError> {
+                Self::parse_expr(input)
+            }
+
+            fn deserialize_full<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                <$ty>::deserialize(deserializer)
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = ParseError;
+
+            fn from_str(input: &str) -> Result<Self, ParseError> {
+                Self::parse_expr(input)
+            }
+        }
+    )*};
+}
+
+expr_serde!(
+    RandF32,
+    RandI32,
+    RandU32,
+    RandBool,
+    RandDuration,
+    RandVec2,
+    RandVec3
+);
+
+enum VectorExpr<const N: usize> {
+    Constant([f32; N]),
+    Scaled {
+        magnitude: RandF32,
+        direction: [f32; N],
+        spread: f32,
+    },
+}
+
+/// Either bound of a range, or a single value
+enum Bounds<T> {
+    Single(T),
+    Range { min: T, max: T, inclusive: bool },
+    PlusMinus { center: T, spread: T },
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    /// Byte offset of the next character
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.position = self.input.len() - self.rest().trim_start().len();
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            position: self.input[..position].chars().count(),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.position, message)
+    }
+
+    /// Consumes `token` if it comes next
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`")))
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.rest() {
+            "" => Ok(()),
+            rest => Err(self.error(format!("unexpected `{rest}`"))),
+        }
+    }
+
+    /// Consumes the longest prefix whose characters all match `accept`
+    fn take_while(&mut self, mut accept: impl FnMut(usize, char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let length = rest
+            .char_indices()
+            .find(|&(index, c)| !accept(index, c))
+            .map_or(rest.len(), |(index, _)| index);
+        self.position += length;
+        &rest[..length]
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let name = self.take_while(|index, c| {
+            c == '_' || c.is_ascii_alphabetic() || (index > 0 && c.is_ascii_digit())
+        });
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Consumes the identifier `name` if it comes next
+    fn eat_identifier(&mut self, name: &str) -> bool {
+        let start = self.position;
+        if self.identifier() == Some(name) {
+            true
+        } else {
+            self.position = start;
+            false
+        }
+    }
+
+    /// Text of the next number, e.g. `-1.5e3`
+    fn number_text(&mut self) -> Result<(usize, &'a str), ParseError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let digits = |parser: &mut Self| !parser.take_while(|_, c| c.is_ascii_digit()).is_empty();
+
+        let _ = self.eat("-") || self.eat("+");
+        let mut valid = digits(self);
+        // Don't mistake the start of a `..` range for a decimal point
+        if self.rest().starts_with('.') && !self.rest().starts_with("..") {
+            self.position += 1;
+            valid |= digits(self);
+        }
+        if valid && (self.rest().starts_with('e') || self.rest().starts_with('E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            let _ = self.eat("-") || self.eat("+");
+            if !digits(self) {
+                self.position = mantissa_end;
+            }
+        }
+
+        if valid {
+            Ok((start, &self.input[start..self.position]))
+        } else {
+            Err(self.error_at(start, "expected a number"))
+        }
+    }
+
+    fn f32(&mut self) -> Result<f32, ParseError> {
+        let (start, text) = self.number_text()?;
+        text.parse()
+            .map_err(|_| self.error_at(start, format!("invalid number `{text}`")))
+    }
+
+    fn integer<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let (start, text) = self.number_text()?;
+        text.parse()
+            .map_err(|_| self.error_at(start, format!("expected an integer, found `{text}`")))
+    }
+
+    /// Angle in radians, or in degrees when followed by `deg` or `°`
+    fn angle(&mut self) -> Result<f32, ParseError> {
+        let value = self.f32()?;
+        if self.eat_identifier("deg") || self.eat("°") {
+            Ok(value.to_radians())
+        } else {
+            self.eat_identifier("rad");
+            Ok(value)
+        }
+    }
+
+    /// Duration, in seconds unless followed by `ms`, `s` or `min`
+    fn duration(&mut self) -> Result<Duration, ParseError> {
+        let (start, text) = self.number_text()?;
+        let unit_nanos: u128 = if self.eat_identifier("ms") {
+            1_000_000
+        } else if self.eat_identifier("min") {
+            60_000_000_000
+        } else {
+            self.eat_identifier("s");
+            1_000_000_000
+        };
+
+        if text.starts_with('-') {
+            return Err(self.error_at(start, "durations can't be negative"));
+        }
+        // Exact decimal arithmetic, so that printed durations read back unchanged
+        let text = text.trim_start_matches('+');
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let invalid = || self.error_at(start, format!("invalid duration `{text}`"));
+        let whole: u128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| invalid())?
+        };
+        let out_of_range = || self.error_at(start, "duration out of range");
+        let fraction_nanos = if fraction.is_empty() {
+            0
+        } else {
+            let digits: u128 = fraction.parse().map_err(|_| invalid())?;
+            let scale = 10u128
+                .checked_pow(fraction.len() as u32)
+                .ok_or_else(invalid)?;
+            digits.checked_mul(unit_nanos).ok_or_else(out_of_range)? / scale
+        };
+
+        let nanos = whole
+            .checked_mul(unit_nanos)
+            .and_then(|nanos| nanos.checked_add(fraction_nanos))
+            .ok_or_else(out_of_range)?;
+        let seconds = u64::try_from(nanos / 1_000_000_000).map_err(|_| out_of_range())?;
+        Ok(Duration::new(seconds, (nanos % 1_000_000_000) as u32))
+    }
+
+    /// `value`, `value..value`, `value..=value` or `value ± value`
+    fn bounds<T>(
+        &mut self,
+        value: impl Fn(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Bounds<T>, ParseError> {
+        let first = value(self)?;
+        if self.eat("..=") {
+            Ok(Bounds::Range {
+                min: first,
+                max: value(self)?,
+                inclusive: true,
+            })
+        } else if self.eat("..") {
+            Ok(Bounds::Range {
+                min: first,
+                max: value(self)?,
+                inclusive: false,
+            })
+        } else if self.eat("±") || self.eat("+-") {
+            Ok(Bounds::PlusMinus {
+                center: first,
+                spread: value(self)?,
+            })
+        } else {
+            Ok(Bounds::Single(first))
+        }
+    }
+
+    fn f32_range(&mut self) -> Result<(f32, f32), ParseError> {
+        let start = self.position;
+        match self.bounds(Self::f32)? {
+            Bounds::Single(_) => Err(self.error_at(start, "expected a range like `1..5`")),
+            Bounds::Range { min, max, .. } => Ok((min, max)),
+            Bounds::PlusMinus { center, spread } => Ok((center - spread, center + spread)),
+        }
+    }
+
+    fn rand_f32(&mut self) -> Result<RandF32, ParseError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let Some(function) = self.identifier() else {
+            let (min, max) = match self.bounds(Self::f32)? {
+                Bounds::Single(value) => (value, value),
+                Bounds::Range { min, max, .. } => (min, max),
+                Bounds::PlusMinus { center, spread } => (center - spread, center + spread),
+            };
+            return Ok(RandF32::uniform(min, max));
+        };
+
+        self.expect("(")?;
+        let value = match function {
+            "normal" => {
+                let range_start = self.position;
+                let first = self.bounds(Self::f32)?;
+                self.expect(",")?;
+                let std_dev = self.f32()?;
+                let (min, max) = match first {
+                    Bounds::Single(mean) => (mean - 3. * std_dev, mean + 3. * std_dev),
+                    Bounds::Range { min, max, .. } => (min, max),
+                    Bounds::PlusMinus { .. } => {
+                        return Err(self.error_at(range_start, "expected a mean or a range"));
+                    }
+                };
+                RandF32::uniform(min, max).with_distribution(F32Distribution::Normal { std_dev })
+            }
+            "triangular" => {
+                let (min, max) = self.f32_range()?;
+                self.expect(",")?;
+                let mode = self.f32()?;
+                RandF32::uniform(min, max).with_distribution(F32Distribution::Triangular { mode })
+            }
+            "exponential" => {
+                let (min, max) = self.f32_range()?;
+                self.expect(",")?;
+                let rate = self.f32()?;
+                RandF32::uniform(min, max).with_distribution(F32Distribution::Exponential { rate })
+            }
+            "log_uniform" => {
+                let (min, max) = self.f32_range()?;
+                RandF32::uniform(min, max).with_distribution(F32Distribution::LogUniform)
+            }
+            _ => {
+                return Err(self.error_at(
+                    start,
+                    format!(
+                        "unknown function `{function}`, expected `normal`, `triangular`, \
+                         `exponential` or `log_uniform`"
+                    ),
+                ));
+            }
+        };
+        self.expect(")")?;
+        Ok(value)
+    }
+
+    /// Integer bounds, with `..` excluding the end
+    fn integer_bounds<T>(&mut self) -> Result<(T, T), ParseError>
+    where
+        T: FromStr + Copy + PartialOrd + TryFrom<i64>,
+        i64: From<T>,
+    {
+        let start = self.position;
+        let (min, max) = match self.bounds(Self::integer::<T>)? {
+            Bounds::Single(value) => return Ok((value, value)),
+            Bounds::Range {
+                min,
+                max,
+                inclusive: true,
+            } => (i64::from(min), i64::from(max)),
+            Bounds::Range {
+                min,
+                max,
+                inclusive: false,
+            } => (i64::from(min), i64::from(max) - 1),
+            Bounds::PlusMinus { center, spread } => {
+                let (center, spread) = (i64::from(center), i64::from(spread));
+                (center - spread, center + spread)
+            }
+        };
+
+        if min > max {
+            return Err(self.error_at(start, "empty range"));
+        }
+        match (T::try_from(min), T::try_from(max)) {
+            (Ok(min), Ok(max)) => Ok((min, max)),
+            _ => Err(self.error_at(start, "range out of bounds")),
+        }
+    }
+
+    /// `(x, y)` or `(x, y, z)`, as many components as `N`
+    fn components<const N: usize>(&mut self) -> Result<[f32; N], ParseError> {
+        self.expect("(")?;
+        let mut components = [0.; N];
+        for (index, component) in components.iter_mut().enumerate() {
+            if index > 0 {
+                self.expect(",")?;
+            }
+            *component = self.f32()?;
+        }
+        self.expect(")")?;
+        Ok(components)
+    }
+
+    /// `(components)` as a constant, or `magnitude * (components)` optionally followed
+    /// by `spread angle`
+    fn vector<const N: usize>(&mut self) -> Result<VectorExpr<N>, ParseError> {
+        self.skip_whitespace();
+        if self.rest().starts_with('(') {
+            return Ok(VectorExpr::Constant(self.components()?));
+        }
+
+        let magnitude = self.rand_f32()?;
+        self.expect("*")?;
+        let direction = self.components()?;
+        let spread = if self.eat_identifier("spread") {
+            self.angle()?
+        } else {
+            0.
+        };
+        Ok(VectorExpr::Scaled {
+            magnitude,
+            direction,
+            spread,
+        })
+    }
+
+    fn choice<T: DeserializeOwned + Send + Sync + TypePath>(
+        &mut self,
+    ) -> Result<RandChoice<T>, ParseError> {
+        if !self.eat_identifier("choice") {
+            return Err(self.error("expected `choice(...)`"));
+        }
+        self.expect("(")?;
+
+        let mut table = RandChoice::default();
+        while !self.eat(")") {
+            self.skip_whitespace();
+            let start = self.position;
+            let entry = if self.eat_identifier("choice") && self.eat("(") {
+                self.position = start;
+                ChoiceEntry::Table(self.choice()?)
+            } else {
+                self.position = start;
+                match self.item_text() {
+                    "" => return Err(self.error("expected an item")),
+                    "_" => ChoiceEntry::Nothing,
+                    text => ChoiceEntry::Item(parse_item(text).ok_or_else(|| {
+                        self.error_at(start, format!("could not read item `{text}`"))
+                    })?),
+                }
+            };
+            let weight = if self.eat(":") { self.f32()? } else { 1. };
+            table.entries.push(WeightedEntry::new(weight, entry));
+
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Ok(table)
+    }
+
+    /// Text up to the next `:`, `,` or `)` outside of brackets and strings
+    fn item_text(&mut self) -> &'a str {
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        let text = self.take_while(|_, c| {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                return true;
+            }
+            match c {
+                '"' => in_string = true,
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth == 0 => return false,
+                ')' | ']' | '}' => depth -= 1,
+                ':' | ',' if depth == 0 => return false,
+                _ => {}
+            }
+            true
+        });
+        text.trim_end()
+    }
+}
+
+/// Reads a choice item as a RON value, or as a plain string, e.g. `Sword` or `sword`
+fn parse_item<T: DeserializeOwned>(text: &str) -> Option<T> {
+    ron::from_str(text).ok().or_else(|| {
+        let deserializer: de::value::StrDeserializer<de::value::Error> = text.into_deserializer();
+        T::deserialize(deserializer).ok()
+    })
+}
+
+/// Item as written by [`parse_item`], as a bare word when possible
+fn item_expr<T: Serialize>(item: &T) -> Option<String> {
+    let text = ron::to_string(item).ok()?;
+    let bare = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .filter(|word| {
+            !word.is_empty()
+                && !["_", "choice", "true", "false"].contains(word)
+                && !word.starts_with(|c: char| c.is_ascii_digit())
+                && word.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+        });
+    Some(bare.map_or(text.clone(), str::to_string))
+}
+
+fn parse_with<'a, T>(
+    input: &'a str,
+    parse: impl FnOnce(&mut Parser<'a>) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let mut parser = Parser::new(input);
+    let value = parse(&mut parser)?;
+    parser.end()?;
+    Ok(value)
+}
+
+fn range_expr<T: fmt::Display + PartialEq>(min: T, max: T, separator: &str) -> String {
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{min}{separator}{max}")
+    }
+}
+
+/// `value` rounded to a few decimals, if it still `reads_back` exactly. Converted
+/// values print with float noise otherwise (0.3 is 30.000002%)
+fn rounded(value: f32, reads_back: impl Fn(f32) -> bool) -> Option<String> {
+    let text = format!("{value:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    text.parse()
+        .ok()
+        .filter(|value| reads_back(*value))
+        .map(|_| text.to_string())
+}
+
+fn duration_expr(duration: Duration) -> String {
+    let nanos = format!("{:09}", duration.subsec_nanos());
+    let fraction = nanos.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}.{fraction}s", duration.as_secs())
+    }
+}
+
+/// `(x, y, z)` for constant vectors, if it `reads_back` to the same value, or
+/// `magnitude * (x, y, z) spread angle` otherwise
+fn vector_expr(
+    magnitude: &RandF32,
+    components: &[f32],
+    spread: f32,
+    reads_back: impl Fn(&[f32]) -> bool,
+) -> Option<String> {
+    // Infinities and NaN don't parse back
+    if !spread.is_finite() || components.iter().any(|c| !c.is_finite()) {
+        return None;
+    }
+    let list = |values: &[String]| format!("({})", values.join(", "));
+
+    if magnitude.min == magnitude.max && spread == 0. {
+        let values: Vec<f32> = components.iter().map(|c| c * magnitude.min).collect();
+        let candidates = [
+            values.iter().map(|v| rounded(*v, |_| true)).collect(),
+            Some(values.iter().map(f32::to_string).collect()),
+        ];
+        let constant = candidates
+            .into_iter()
+            .flatten()
+            .find(|texts: &Vec<String>| {
+                let parsed: Vec<f32> = texts.iter().filter_map(|text| text.parse().ok()).collect();
+                parsed.len() == texts.len() && reads_back(&parsed)
+            });
+        if let Some(texts) = constant {
+            return Some(list(&texts));
+        }
+    }
+
+    let components: Vec<String> = components.iter().map(f32::to_string).collect();
+    let mut expr = format!("{} * {}", magnitude.to_expr()?, list(&components));
+    if spread != 0. {
+        let angle = rounded(spread.to_degrees(), |degrees| {
+            degrees.to_radians() == spread
+        })
+        .map_or_else(|| format!("{spread}rad"), |degrees| format!("{degrees}deg"));
+        expr += &format!(" spread {angle}");
+    }
+    Some(expr)
+}
+
+impl RandExpr for RandF32 {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, Parser::rand_f32)
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        let (min, max) = (self.min, self.max);
+        let parameter = match self.distribution {
+            F32Distribution::Normal { std_dev } => std_dev,
+            F32Distribution::Triangular { mode } => mode,
+            F32Distribution::Exponential { rate } => rate,
+            _ => 0.,
+        };
+        // Infinities and NaN don't parse back
+        if !(min.is_finite() && max.is_finite() && parameter.is_finite()) {
+            return None;
+        }
+
+        let range = format!("{min}..{max}");
+        Some(match &self.distribution {
+            F32Distribution::Uniform => range_expr(min, max, ".."),
+            F32Distribution::Normal { std_dev } => {
+                let mean = (min + max) / 2.;
+                if mean - 3. * std_dev == min && mean + 3. * std_dev == max {
+                    format!("normal({mean}, {std_dev})")
+                } else {
+                    format!("normal({range}, {std_dev})")
+                }
+            }
+            F32Distribution::Triangular { mode } => format!("triangular({range}, {mode})"),
+            F32Distribution::Exponential { rate } => format!("exponential({range}, {rate})"),
+            F32Distribution::LogUniform => format!("log_uniform({range})"),
+            F32Distribution::Curve(_) => return None,
+        })
+    }
+}
+
+impl RandExpr for RandI32 {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            let (min, max) = parser.integer_bounds()?;
+            Ok(Self { min, max })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        Some(range_expr(self.min, self.max, "..="))
+    }
+}
+
+impl RandExpr for RandU32 {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            let (min, max) = parser.integer_bounds()?;
+            Ok(Self { min, max })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        Some(range_expr(self.min, self.max, "..="))
+    }
+}
+
+impl RandExpr for RandBool {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            if parser.eat_identifier("true") {
+                return Ok(Self::constant(true));
+            }
+            if parser.eat_identifier("false") {
+                return Ok(Self::constant(false));
+            }
+
+            parser.skip_whitespace();
+            let start = parser.position;
+            let mut probability = parser.f32()?;
+            if parser.eat("%") {
+                probability /= 100.;
+            }
+            if !(0.0..=1.).contains(&probability) {
+                return Err(parser.error_at(start, "probability must be between 0% and 100%"));
+            }
+            Ok(Self { probability })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        let probability = self.probability;
+        if !probability.is_finite() {
+            return None;
+        }
+        if probability == 0. || probability == 1. {
+            return Some((probability == 1.).to_string());
+        }
+
+        Some(
+            rounded(probability * 100., |percent| percent / 100. == probability)
+                .map_or_else(|| probability.to_string(), |percent| format!("{percent}%")),
+        )
+    }
+}
+
+impl RandExpr for RandDuration {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            parser.skip_whitespace();
+            let start = parser.position;
+            let (min, max) = match parser.bounds(Parser::duration)? {
+                Bounds::Single(value) => (value, value),
+                Bounds::Range { min, max, .. } => (min, max),
+                Bounds::PlusMinus { center, spread } => {
+                    let max = center
+                        .checked_add(spread)
+                        .ok_or_else(|| parser.error_at(start, "duration out of range"))?;
+                    (center.saturating_sub(spread), max)
+                }
+            };
+            Ok(Self { min, max })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        Some(range_expr(
+            duration_expr(self.min),
+            duration_expr(self.max),
+            "..",
+        ))
+    }
+}
+
+impl RandExpr for RandVec3 {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            let (magnitude, direction, spread) = match parser.vector()? {
+                VectorExpr::Constant(value) => return Ok(Self::constant(Vec3::from_array(value))),
+                VectorExpr::Scaled {
+                    magnitude,
+                    direction,
+                    spread,
+                } => (magnitude, direction, spread),
+            };
+
+            let distribution = match parser.identifier() {
+                None | Some("uniform_angle") => SpreadDistribution::UniformAngle,
+                Some("solid_angle") => SpreadDistribution::UniformSolidAngle,
+                Some("gaussian") => SpreadDistribution::Gaussian,
+                Some(other) => {
+                    return Err(parser.error_at(
+                        parser.position - other.len(),
+                        format!(
+                            "unknown spread `{other}`, expected `uniform_angle`, \
+                             `solid_angle` or `gaussian`"
+                        ),
+                    ));
+                }
+            };
+            Ok(Self {
+                magnitude,
+                direction: Vec3::from_array(direction),
+                spread,
+                distribution,
+            })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        let mut expr = vector_expr(
+            &self.magnitude,
+            &self.direction.to_array(),
+            self.spread,
+            |value| {
+                let constant = Self::constant(Vec3::from_slice(value));
+                constant.direction == self.direction && constant.magnitude.min == self.magnitude.min
+            },
+        )?;
+        // Without a spread, the distribution makes no difference
+        match self.distribution {
+            _ if self.spread == 0. => {}
+            SpreadDistribution::UniformAngle => {}
+            SpreadDistribution::UniformSolidAngle => expr += " solid_angle",
+            SpreadDistribution::Gaussian => expr += " gaussian",
+        }
+        Some(expr)
+    }
+}
+
+impl RandExpr for RandVec2 {
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, |parser| {
+            Ok(match parser.vector()? {
+                VectorExpr::Constant(value) => Self::constant(Vec2::from_array(value)),
+                VectorExpr::Scaled {
+                    magnitude,
+                    direction,
+                    spread,
+                } => Self {
+                    magnitude,
+                    direction: Vec2::from_array(direction),
+                    spread,
+                },
+            })
+        })
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        vector_expr(
+            &self.magnitude,
+            &self.direction.to_array(),
+            self.spread,
+            |value| {
+                let constant = Self::constant(Vec2::from_slice(value));
+                constant.direction == self.direction && constant.magnitude.min == self.magnitude.min
+            },
+        )
+    }
+}
+
+fn choice_expr<T: Serialize + Send + Sync + TypePath>(table: &RandChoice<T>) -> Option<String> {
+    if !table.guaranteed.is_empty() || table.picks != 1 || table.unique {
+        return None;
+    }
+
+    let entries = table
+        .entries
+        .iter()
+        .map(|WeightedEntry { weight, entry }| {
+            let entry = match entry {
+                ChoiceEntry::Item(item) => item_expr(item)?,
+                ChoiceEntry::Table(table) => choice_expr(table)?,
+                ChoiceEntry::Nothing => "_".to_string(),
+            };
+            Some(if *weight == 1. {
+                entry
+            } else {
+                format!("{entry}: {weight}")
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("choice({})", entries.join(", ")))
+}
+
+impl<T> RandExpr for RandChoice<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + TypePath,
+{
+    fn parse_expr(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, Parser::choice)
+    }
+
+    fn to_expr(&self) -> Option<String> {
+        choice_expr(self)
+    }
+}
+
+impl<T: Serialize + Send + Sync + TypePath> Serialize for RandChoice<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match choice_expr(self).filter(|_| serializer.is_human_readable()) {
+            Some(expr) => serializer.serialize_str(&expr),
+            None => RandChoice::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned + Send + Sync + TypePath> Deserialize<'de> for RandChoice<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_expr(deserializer)
+    }
+}
+
+impl<T: DeserializeOwned + Send + Sync + TypePath> ExprDeserialize for RandChoice<T> {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, Parser::choice)
+    }
+
+    fn deserialize_full<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RandChoice::deserialize(deserializer)
+    }
+}
+
+impl<T: DeserializeOwned + Send + Sync + TypePath> FromStr for RandChoice<T> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        parse_with(input, Parser::choice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::RandPitchYaw;
+
+    #[test]
+    fn scalars() {
+        let value: RandF32 = "3..7".parse().unwrap();
+        assert_eq!((value.min, value.max), (3., 7.));
+
+        let value: RandF32 = "5 ± 1.5".parse().unwrap();
+        assert_eq!((value.min, value.max), (3.5, 6.5));
+        let value: RandF32 = " -2 +- 0.5e1 ".parse().unwrap();
+        assert_eq!((value.min, value.max), (-7., 3.));
+
+        let value: RandF32 = "normal(10, 2)".parse().unwrap();
+        assert_eq!((value.min, value.max), (4., 16.));
+        assert!(matches!(
+            value.distribution,
+            F32Distribution::Normal { std_dev: 2. }
+        ));
+
+        let value: RandI32 = "-3..3".parse().unwrap();
+        assert_eq!((value.min, value.max), (-3, 2));
+        let value: RandU32 = "5 ± 2".parse().unwrap();
+        assert_eq!((value.min, value.max), (3, 7));
+
+        let value: RandBool = "25%".parse().unwrap();
+        assert_eq!(value.probability, 0.25);
+
+        let value: RandDuration = "500ms ± 100ms".parse().unwrap();
+        assert_eq!(value.min, Duration::from_millis(400));
+        assert_eq!(value.max, Duration::from_millis(600));
+        let value: RandDuration = "1.5..1min".parse().unwrap();
+        assert_eq!(value.min, Duration::from_millis(1500));
+        assert_eq!(value.max, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn vectors() {
+        let value: RandVec3 = "2..5 * (0, 1, 0) spread 30deg solid_angle".parse().unwrap();
+        assert_eq!((value.magnitude.min, value.magnitude.max), (2., 5.));
+        assert_eq!(value.direction, Vec3::Y);
+        assert!((value.spread - 30f32.to_radians()).abs() < 1e-6);
+        assert_eq!(value.distribution, SpreadDistribution::UniformSolidAngle);
+
+        let value: RandVec2 = "(3, 4)".parse().unwrap();
+        assert_eq!(value.generate(), Vec2::new(3., 4.));
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let err = "3..".parse::<RandF32>().unwrap_err();
+        assert_eq!(err.message, "expected a number");
+        assert_eq!(err.position, 3);
+
+        let err = "gamma(1, 2)".parse::<RandF32>().unwrap_err();
+        assert!(err.message.starts_with("unknown function `gamma`"));
+        assert_eq!(err.position, 0);
+
+        let err = "1 ± 2 3".parse::<RandF32>().unwrap_err();
+        assert_eq!(err.to_string(), "unexpected `3` at column 7");
+
+        let err = "5..5".parse::<RandU32>().unwrap_err();
+        assert_eq!(err.message, "empty range");
+
+        let err = "2 * (1, 2".parse::<RandVec3>().unwrap_err();
+        assert_eq!((err.message.as_str(), err.position), ("expected `,`", 9));
+
+        for input in [
+            "18446744073709551615s ± 1s",
+            "1 ± 18446744073709551616s",
+            "999999999999999999999999999999min",
+        ] {
+            let err = input.parse::<RandDuration>().unwrap_err();
+            assert_eq!(err.message, "duration out of range", "{input}");
+        }
+        let err = "1s..18446744073709551616s"
+            .parse::<RandDuration>()
+            .unwrap_err();
+        assert_eq!(
+            (err.message.as_str(), err.position),
+            ("duration out of range", 4)
+        );
+
+        let err = ron::from_str::<RandF32>("\"1..x\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid expression `1..x`: expected a number at column 4")
+        );
+    }
+
+    #[test]
+    fn short_and_full_forms_in_ron() {
+        let value: RandPitchYaw = ron::from_str(
+            "(
+                yaw: \"-1..1\",
+                pitch: (min: 0.0, max: 0.5, distribution: Normal(std_dev: 0.1)),
+            )",
+        )
+        .unwrap();
+        assert_eq!((value.yaw.min, value.yaw.max), (-1., 1.));
+        assert!(matches!(
+            value.pitch.distribution,
+            F32Distribution::Normal { std_dev: 0.1 }
+        ));
+
+        let value: RandVec3 = ron::from_str(
+            "(magnitude: 2.0, direction: (0.0, 0.0, 1.0), spread: 0.0, distribution: Gaussian)",
+        )
+        .unwrap();
+        assert_eq!(value.magnitude.max, 2.);
+        assert_eq!(value.distribution, SpreadDistribution::Gaussian);
+    }
+
+    #[test]
+    fn serializes_to_canonical_form() {
+        let cases = [
+            ("3..7", "3..7"),
+            ("5 ± 1.5", "3.5..6.5"),
+            ("normal(10, 2)", "normal(10, 2)"),
+            ("normal(0..1, 2)", "normal(0..1, 2)"),
+            ("log_uniform(20..2000)", "log_uniform(20..2000)"),
+        ];
+        for (input, canonical) in cases {
+            let value: RandF32 = input.parse().unwrap();
+            assert_eq!(value.to_expr().unwrap(), canonical);
+            assert_eq!(ron::to_string(&value).unwrap(), format!("\"{canonical}\""));
+        }
+
+        let expr = |input: &str| input.parse::<RandDuration>().unwrap().to_expr().unwrap();
+        assert_eq!(expr("250ms..1.5"), "0.25s..1.5s");
+        assert_eq!(expr("2min"), "120s");
+
+        let expr = |input: &str| input.parse::<RandBool>().unwrap().to_expr().unwrap();
+        assert_eq!(expr("0.3"), "30%");
+        assert_eq!(expr("100%"), "true");
+        assert_eq!(expr("0.123456789"), "0.12345679");
+
+        assert_eq!("4".parse::<RandI32>().unwrap().to_expr().unwrap(), "4");
+        assert_eq!(
+            "1..4".parse::<RandU32>().unwrap().to_expr().unwrap(),
+            "1..=3"
+        );
+
+        let value: RandVec3 = "2..5 * (0, 1, 0) spread 30deg gaussian".parse().unwrap();
+        assert_eq!(
+            value.to_expr().unwrap(),
+            "2..5 * (0, 1, 0) spread 30deg gaussian"
+        );
+
+        let cases = [
+            ("(1, 1, 1)", "(1, 1, 1)"),
+            ("(0, -2.5, 0)", "(0, -2.5, 0)"),
+            ("3 * (0, 0, 1)", "(0, 0, 3)"),
+            ("2..5 * (0, 1, 0)", "2..5 * (0, 1, 0)"),
+        ];
+        for (input, canonical) in cases {
+            let value: RandVec3 = input.parse().unwrap();
+            assert_eq!(value.to_expr().unwrap(), canonical);
+            assert_eq!(ron::to_string(&value).unwrap(), format!("\"{canonical}\""));
+        }
+        let value: RandVec2 = "(0.1, 0.2)".parse().unwrap();
+        assert_eq!(value.to_expr().unwrap(), "(0.1, 0.2)");
+    }
+
+    #[test]
+    fn non_finite_values_use_the_full_form() {
+        let values = [
+            RandF32::uniform(0., f32::INFINITY),
+            RandF32::constant(f32::NAN),
+            RandF32::uniform(0., 1.).with_distribution(F32Distribution::Normal {
+                std_dev: f32::INFINITY,
+            }),
+        ];
+        for value in values {
+            assert!(value.to_expr().is_none());
+            let text = ron::to_string(&value).unwrap();
+            assert!(text.starts_with('('), "{text}");
+        }
+
+        let value = RandVec3::constant(Vec3::new(f32::NAN, 0., 0.));
+        assert!(value.to_expr().is_none());
+        let value = RandVec3 {
+            spread: f32::INFINITY,
+            ..RandVec3::constant(Vec3::Y)
+        };
+        assert!(value.to_expr().is_none());
+        let value = RandVec2::constant(Vec2::new(f32::NEG_INFINITY, 1.));
+        assert!(value.to_expr().is_none());
+        let value = RandBool {
+            probability: f32::NAN,
+        };
+        assert!(value.to_expr().is_none());
+
+        let curve =
+            bevy::math::curve::SampleAutoCurve::new(bevy::math::curve::Interval::UNIT, [0., 1.])
+                .unwrap();
+        let value = RandF32::uniform(0., 1.)
+            .with_distribution(F32Distribution::Curve(std::sync::Arc::new(curve)));
+        assert!(value.to_expr().is_none());
+        let text = ron::to_string(&value).unwrap();
+        let loaded: RandF32 = ron::from_str(&text).unwrap();
+        assert!(matches!(loaded.distribution, F32Distribution::Curve(_)));
+    }
+
+    #[test]
+    fn choices() {
+        let table: RandChoice<String> = "choice(a: 3, b)".parse().unwrap();
+        assert_eq!(table.entries.len(), 2);
+        assert_eq!(table.entries[0].weight, 3.);
+        assert_eq!(table.entries[1].entry, ChoiceEntry::Item("b".to_string()));
+        assert_eq!(table.to_expr().unwrap(), "choice(a: 3, b)");
+
+        let table: RandChoice<String> =
+            r#"choice("two words": 2, _: 5, choice(ruby, emerald): 0.5)"#
+                .parse()
+                .unwrap();
+        assert_eq!(table.entries[1].entry, ChoiceEntry::Nothing);
+        assert!(
+            matches!(&table.entries[2].entry, ChoiceEntry::Table(gems) if gems.entries.len() == 2)
+        );
+        assert_eq!(
+            ron::to_string(&table).unwrap(),
+            r#""choice(\"two words\": 2, _: 5, choice(ruby, emerald): 0.5)""#
+        );
+
+        let err = "choice(a: 1, b".parse::<RandChoice<String>>().unwrap_err();
+        assert_eq!((err.message.as_str(), err.position), ("expected `)`", 14));
+
+        let table: RandChoice<String> =
+            ron::from_str("(entries: [(entry: Table(\"choice(x, y)\"))], picks: 2)").unwrap();
+        assert!(table.to_expr().is_none());
+        assert!(
+            matches!(&table.entries[0].entry, ChoiceEntry::Table(inner) if inner.entries.len() == 2)
+        );
+    }
+}
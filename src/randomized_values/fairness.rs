@@ -0,0 +1,265 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Deals its items in random order, handing out every item once before any of them
+/// repeats. Avoids the streaks and droughts of independent random picks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShuffleBag<T> {
+    pub items: Vec<T>,
+    /// Indices into `items` not dealt yet in the current round
+    remaining: Vec<usize>,
+}
+
+impl<T: Clone> ShuffleBag<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            remaining: Vec::new(),
+        }
+    }
+
+    /// Deals the next item, starting a new round once all of them were dealt. Returns
+    /// `None` if the bag has no items.
+    pub fn deal_with<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        self.remaining.retain(|index| *index < self.items.len());
+        if self.remaining.is_empty() {
+            self.remaining.extend(0..self.items.len());
+        }
+
+        let picked = self
+            .remaining
+            .swap_remove(rng.random_range(0..self.remaining.len()));
+        Some(self.items[picked].clone())
+    }
+
+    /// Deals the next item using the thread RNG
+    pub fn deal(&mut self) -> Option<T> {
+        self.deal_with(&mut rand::rng())
+    }
+
+    /// Items left before the current round ends
+    pub fn remaining(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Puts every item back in the bag
+    pub fn reset(&mut self) {
+        self.remaining.clear();
+    }
+}
+
+/// Boolean roll whose chance starts low and grows after each failure, resetting on
+/// success (pseudo-random distribution). Successes average out to `probability`, but
+/// long streaks of failures or successes are much rarer than with independent rolls.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "PseudoRandomBoolData")]
+pub struct PseudoRandomBool {
+    /// Average chance of success
+    probability: f32,
+    /// Chance gained on each failure, and chance of the first roll. Derived from
+    /// `probability`
+    #[serde(skip_serializing)]
+    increment: f32,
+    /// Failures since the last success
+    pub failures: u32,
+}
+
+/// Serialized form of [`PseudoRandomBool`], without the derived increment
+#[derive(Deserialize)]
+struct PseudoRandomBoolData {
+    probability: f32,
+    #[serde(default)]
+    failures: u32,
+}
+
+impl From<PseudoRandomBoolData> for PseudoRandomBool {
+    fn from(data: PseudoRandomBoolData) -> Self {
+        Self {
+            failures: data.failures,
+            ..Self::new(data.probability)
+        }
+    }
+}
+
+impl PseudoRandomBool {
+    pub fn new(probability: f32) -> Self {
+        Self {
+            probability,
+            increment: prd_increment(probability),
+            failures: 0,
+        }
+    }
+
+    /// Average chance of success
+    pub fn probability(&self) -> f32 {
+        self.probability
+    }
+
+    /// Changes the average chance of success, keeping the current failure streak
+    pub fn set_probability(&mut self, probability: f32) {
+        self.probability = probability;
+        self.increment = prd_increment(probability);
+    }
+
+    /// Chance gained on each failure, and chance of the first roll
+    pub fn increment(&self) -> f32 {
+        self.increment
+    }
+
+    /// Chance of success of the next roll
+    pub fn chance(&self) -> f32 {
+        (self.increment * (self.failures + 1) as f32).min(1.)
+    }
+
+    pub fn roll_with<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let success = rng.random::<f32>() < self.chance();
+        if success {
+            self.failures = 0;
+        } else {
+            self.failures += 1;
+        }
+        success
+    }
+
+    /// Rolls using the thread RNG
+    pub fn roll(&mut self) -> bool {
+        self.roll_with(&mut rand::rng())
+    }
+
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+/// Average chance of success of a pseudo-random distribution gaining `increment` on
+/// each failure
+fn prd_probability(increment: f64) -> f64 {
+    let mut expected_rolls = 0.;
+    let mut not_yet = 1.;
+    let mut rolls = 1.;
+
+    // Stop once the remaining chance of failing that long is negligible, rather than
+    // when the chance reaches 1, which takes `1 / increment` rolls
+    while not_yet > 1e-15 {
+        let chance = (increment * rolls).min(1.);
+        expected_rolls += rolls * not_yet * chance;
+        not_yet *= 1. - chance;
+        rolls += 1.;
+    }
+
+    1. / expected_rolls
+}
+
+/// Increment giving an average chance of success of `probability`
+fn prd_increment(probability: f32) -> f32 {
+    let probability = probability.clamp(0., 1.) as f64;
+    if probability <= 0. || probability >= 1. {
+        return probability as f32;
+    }
+
+    // The average chance grows with the increment, and is always above it. Small
+    // probabilities need an increment close to `PI / 2 * probability^2`, start from
+    // slightly above that
+    let estimate = (2. * probability * probability).min(probability);
+    let upper = if prd_probability(estimate) >= probability {
+        estimate
+    } else {
+        probability
+    };
+    let (mut low, mut high) = (0., upper);
+    for _ in 0..50 {
+        let mid = (low + high) / 2.;
+        if prd_probability(mid) < probability {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    ((low + high) / 2.) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::rng::GlobalRng;
+
+    #[test]
+    fn bag_deals_everything_before_repeating() {
+        let mut bag = ShuffleBag::new(vec![1, 2, 3, 4, 5]);
+        let mut rng = GlobalRng::from_seed(3);
+
+        for _ in 0..10 {
+            let mut round: Vec<i32> = (0..5).map(|_| bag.deal_with(&mut rng).unwrap()).collect();
+            round.sort();
+            assert_eq!(round, [1, 2, 3, 4, 5]);
+        }
+        assert_eq!(ShuffleBag::<i32>::new(Vec::new()).deal(), None);
+    }
+
+    #[test]
+    fn prd_matches_average_probability() {
+        for probability in [0.05, 0.25, 0.5, 0.8] {
+            let mut prd = PseudoRandomBool::new(probability);
+            let mut rng = GlobalRng::from_seed(3);
+
+            let rolls = 50000;
+            let successes = (0..rolls).filter(|_| prd.roll_with(&mut rng)).count();
+            let measured = successes as f32 / rolls as f32;
+            assert!(
+                (measured - probability).abs() < 0.01,
+                "Expected {probability}, got {measured}"
+            );
+        }
+    }
+
+    #[test]
+    fn prd_bounds_failure_streaks() {
+        let mut prd = PseudoRandomBool::new(0.25);
+        let max_streak = (1. / prd.increment()).ceil() as u32;
+        let mut rng = GlobalRng::from_seed(3);
+
+        for _ in 0..10000 {
+            prd.roll_with(&mut rng);
+            assert!(prd.failures < max_streak);
+        }
+    }
+
+    #[test]
+    fn prd_handles_small_probabilities() {
+        let probability = 1e-4;
+        let prd = PseudoRandomBool::new(probability);
+
+        let expected = std::f32::consts::PI / 2. * probability * probability;
+        assert!(
+            (prd.increment() / expected - 1.).abs() < 0.01,
+            "{}",
+            prd.increment()
+        );
+        assert!((prd_probability(prd.increment() as f64) as f32 / probability - 1.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn prd_increment_follows_probability() {
+        let mut prd = PseudoRandomBool::new(0.25);
+        prd.failures = 2;
+        prd.set_probability(0.5);
+        assert_eq!(
+            prd,
+            PseudoRandomBool {
+                failures: 2,
+                ..PseudoRandomBool::new(0.5)
+            }
+        );
+
+        let text = ron::to_string(&prd).unwrap();
+        assert!(!text.contains("increment"), "{text}");
+        assert_eq!(ron::from_str::<PseudoRandomBool>(&text).unwrap(), prd);
+
+        let loaded: PseudoRandomBool = ron::from_str("(probability: 0.25)").unwrap();
+        assert_eq!(loaded, PseudoRandomBool::new(0.25));
+    }
+}
@@ -1,49 +1,166 @@
 use bevy::prelude::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::f32::consts::PI;
+use std::{f32::consts::PI, sync::Arc, time::Duration};
+
+use crate::geometric::pitchyaw::PitchYaw;
+
+pub mod choice;
+pub mod curves;
+pub mod expr;
+pub mod fairness;
+pub mod poisson;
+pub mod rng;
+mod sampling;
+pub mod shapes;
+pub mod spawn;
 
 pub trait RandValue {
     type Out;
 
-    fn generate(&self) -> Self::Out;
+    /// Draws a value from `rng`, e.g. a seeded [`GlobalRng`](rng::GlobalRng) or
+    /// [`EntityRng`](rng::EntityRng) for reproducible sequences
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Out;
     fn constant(value: Self::Out) -> Self;
+
+    /// Draws a value from the thread RNG
+    fn generate(&self) -> Self::Out {
+        self.generate_with(&mut rand::rng())
+    }
 }
 
-#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+/// How [`RandF32`] distributes values within `[min, max]`
+#[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize)]
+pub enum F32Distribution {
+    #[default]
+    Uniform,
+    /// Normal distribution centered between `min` and `max`, with tails clamped to
+    /// the range
+    Normal { std_dev: f32 },
+    /// Peaks at `mode`, falling linearly to zero at `min` and `max`
+    Triangular { mode: f32 },
+    /// Exponential distribution starting at `min`, clamped to `max`
+    Exponential { rate: f32 },
+    /// Uniform in the logarithm of the value, e.g. for frequencies or scales. `min`
+    /// and `max` must be positive
+    LogUniform,
+    /// Inverse CDF: maps a uniform sample in `[0, 1]`, stretched over the curve's
+    /// domain, to the fraction of the way from `min` to `max`. Shared, so that
+    /// cloning stays cheap
+    Curve(Arc<SampleAutoCurve<f32>>),
+}
+
+#[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct RandF32 {
     pub min: f32,
     pub max: f32,
+    /// Missing from older data files, which were always uniform
+    #[serde(default)]
+    pub distribution: F32Distribution,
+}
+
+impl RandF32 {
+    pub fn uniform(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            distribution: F32Distribution::Uniform,
+        }
+    }
+
+    pub fn with_distribution(self, distribution: F32Distribution) -> Self {
+        Self {
+            distribution,
+            ..self
+        }
+    }
 }
 
 impl RandValue for RandF32 {
     type Out = f32;
 
-    fn generate(&self) -> f32 {
-        rand::random::<f32>() * (self.max - self.min) + self.min
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let (min, max) = (self.min, self.max);
+        let u = rng.random::<f32>();
+
+        match &self.distribution {
+            F32Distribution::Uniform => u * (max - min) + min,
+            F32Distribution::Normal { std_dev } => {
+                // Box-Muller transform
+                let radius = (-2. * (1. - u).ln()).sqrt();
+                let normal = radius * (2. * PI * rng.random::<f32>()).cos();
+                ((min + max) / 2. + normal * std_dev).clamp(min.min(max), max.max(min))
+            }
+            F32Distribution::Triangular { mode } => {
+                let mode = mode.clamp(min.min(max), max.max(min));
+                let range = max - min;
+                if range == 0. {
+                    min
+                } else if u < (mode - min) / range {
+                    min + (u * range * (mode - min)).sqrt()
+                } else {
+                    max - ((1. - u) * range * (max - mode)).sqrt()
+                }
+            }
+            F32Distribution::Exponential { rate } => (min - (1. - u).ln() / rate).min(max.max(min)),
+            F32Distribution::LogUniform => (min.ln() + u * (max.ln() - min.ln())).exp(),
+            F32Distribution::Curve(curve) => {
+                let domain = curve.domain();
+                let t = domain.start() + u * domain.length();
+                min + (max - min) * curve.sample_clamped(t)
+            }
+        }
     }
 
     fn constant(value: f32) -> Self {
-        Self {
-            min: value,
-            max: value,
-        }
+        Self::uniform(value, value)
     }
 }
 
-#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+/// How [`RandVec3`] picks directions within its spread cone
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpreadDistribution {
+    /// Angle from the axis is uniform in `[0, spread]`. Clusters directions near the
+    /// axis, since the cone gets wider further out
+    #[default]
+    UniformAngle,
+    /// Uniform over the solid angle of the spherical cap of half-angle `spread`
+    UniformSolidAngle,
+    /// Normal distribution around the axis, with `spread` as the standard deviation
+    /// in radians of each tangent component. Not bounded to a cone
+    Gaussian,
+}
+
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct RandVec3 {
     pub magnitude: RandF32,
     pub direction: Vec3,
     pub spread: f32,
+    #[serde(default)]
+    pub distribution: SpreadDistribution,
 }
 
 impl RandValue for RandVec3 {
     type Out = Vec3;
 
-    fn generate(&self) -> Vec3 {
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
         let dir = if self.spread > 0. {
-            let spread_angle = rand::random::<f32>() * 2. * PI;
-            let spread_radius = rand::random::<f32>() * self.spread;
+            let spread_angle = rng.random::<f32>() * 2. * PI;
+            let spread_radius = match self.distribution {
+                SpreadDistribution::UniformAngle => rng.random::<f32>() * self.spread,
+                SpreadDistribution::UniformSolidAngle => {
+                    let cos_spread = self.spread.min(PI).cos();
+                    (1. - rng.random::<f32>() * (1. - cos_spread)).acos()
+                }
+                SpreadDistribution::Gaussian => {
+                    // Distance from the axis of a 2D normal sample follows a Rayleigh
+                    // distribution
+                    let u = rng.random::<f32>();
+                    (self.spread * (-2. * (1. - u).ln()).sqrt()).min(PI)
+                }
+            };
 
             let local_dir = Quat::from_rotation_x(spread_angle)
                 * Vec3::new(spread_radius.cos(), 0., spread_radius.sin());
@@ -53,7 +170,7 @@ impl RandValue for RandVec3 {
             self.direction.normalize_or_zero()
         };
 
-        dir * self.magnitude.generate()
+        dir * self.magnitude.generate_with(rng)
     }
 
     fn constant(value: Vec3) -> Self {
@@ -61,6 +178,7 @@ impl RandValue for RandVec3 {
             direction: value.normalize_or_zero(),
             magnitude: RandF32::constant(value.length()),
             spread: 0.,
+            distribution: SpreadDistribution::default(),
         }
     }
 }
@@ -71,6 +189,518 @@ impl Default for RandVec3 {
             magnitude: RandF32::default(),
             direction: Vec3::X,
             spread: 0.,
+            distribution: SpreadDistribution::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct RandVec2 {
+    pub magnitude: RandF32,
+    pub direction: Vec2,
+    /// Maximum angle away from `direction`, to either side
+    pub spread: f32,
+}
+
+impl RandValue for RandVec2 {
+    type Out = Vec2;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let dir = self.direction.normalize_or_zero();
+        let dir = if self.spread > 0. {
+            let angle = (rng.random::<f32>() * 2. - 1.) * self.spread;
+            Vec2::from_angle(angle).rotate(dir)
+        } else {
+            dir
+        };
+
+        dir * self.magnitude.generate_with(rng)
+    }
+
+    fn constant(value: Vec2) -> Self {
+        Self {
+            direction: value.normalize_or_zero(),
+            magnitude: RandF32::constant(value.length()),
+            spread: 0.,
+        }
+    }
+}
+
+impl Default for RandVec2 {
+    fn default() -> Self {
+        Self {
+            magnitude: RandF32::default(),
+            direction: Vec2::X,
+            spread: 0.,
+        }
+    }
+}
+
+/// Integer in `[min, max]`, both ends included
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct RandI32 {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl RandValue for RandI32 {
+    type Out = i32;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> i32 {
+        rng.random_range(self.min.min(self.max)..=self.max.max(self.min))
+    }
+
+    fn constant(value: i32) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+}
+
+/// Integer in `[min, max]`, both ends included
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct RandU32 {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl RandValue for RandU32 {
+    type Out = u32;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        rng.random_range(self.min.min(self.max)..=self.max.max(self.min))
+    }
+
+    fn constant(value: u32) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+}
+
+/// `true` with the given probability, clamped to `[0, 1]`
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct RandBool {
+    pub probability: f32,
+}
+
+impl RandValue for RandBool {
+    type Out = bool;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        rng.random_bool(self.probability.clamp(0., 1.) as f64)
+    }
+
+    fn constant(value: bool) -> Self {
+        Self {
+            probability: if value { 1. } else { 0. },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct RandDuration {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl RandValue for RandDuration {
+    type Out = Duration;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let min = self.min.min(self.max);
+        let range = self.max.max(self.min) - min;
+        min + range.mul_f32(rng.random::<f32>())
+    }
+
+    fn constant(value: Duration) -> Self {
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+}
+
+/// Rotation at most `max_angle` radians away from `base`, uniformly distributed over
+/// that set of rotations. A `max_angle` of `PI` or more gives a uniformly random
+/// rotation.
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+pub struct RandQuat {
+    pub base: Quat,
+    pub max_angle: f32,
+}
+
+impl RandQuat {
+    pub fn uniform() -> Self {
+        Self {
+            base: Quat::IDENTITY,
+            max_angle: PI,
         }
     }
 }
+
+impl RandValue for RandQuat {
+    type Out = Quat;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Quat {
+        if self.max_angle <= 0. {
+            return self.base;
+        }
+
+        if self.max_angle >= PI {
+            // Shoemake's method
+            let (u1, u2, u3) = (
+                rng.random::<f32>(),
+                rng.random::<f32>(),
+                rng.random::<f32>(),
+            );
+            let (a, b) = ((1. - u1).sqrt(), u1.sqrt());
+            let offset = Quat::from_xyzw(
+                a * (2. * PI * u2).sin(),
+                a * (2. * PI * u2).cos(),
+                b * (2. * PI * u3).sin(),
+                b * (2. * PI * u3).cos(),
+            );
+            return (self.base * offset).normalize();
+        }
+
+        let z = rng.random::<f32>() * 2. - 1.;
+        let azimuth = rng.random::<f32>() * 2. * PI;
+        let xy = (1. - z * z).max(0.).sqrt();
+        let axis = Vec3::new(xy * azimuth.cos(), xy * azimuth.sin(), z);
+
+        // Uniform rotations have their angle distributed proportionally to
+        // 1 - cos(angle), sample it by rejection
+        let max_density = 1. - self.max_angle.cos();
+        let angle = loop {
+            let angle = rng.random::<f32>() * self.max_angle;
+            if rng.random::<f32>() * max_density <= 1. - angle.cos() {
+                break angle;
+            }
+        };
+
+        (self.base * Quat::from_axis_angle(axis, angle)).normalize()
+    }
+
+    fn constant(value: Quat) -> Self {
+        Self {
+            base: value,
+            max_angle: 0.,
+        }
+    }
+}
+
+/// Color space in which [`RandColor`] interpolates its channels
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RandColorSpace {
+    #[default]
+    Hsv,
+    Oklch,
+}
+
+/// Color with each channel picked independently between the matching channels of
+/// `min` and `max`, in the given color space. Hue goes up from `min` to `max`,
+/// wrapping around 360 degrees if `max` has a lower hue.
+#[derive(Debug, Clone, Copy, Reflect, Default, Serialize, Deserialize)]
+pub struct RandColor {
+    pub min: Color,
+    pub max: Color,
+    pub space: RandColorSpace,
+}
+
+impl RandValue for RandColor {
+    type Out = Color;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Color {
+        let mut channel = |min: f32, max: f32| min + (max - min) * rng.random::<f32>();
+        let hue = |min: f32, max: f32, t: f32| {
+            let range = if max < min {
+                max + 360. - min
+            } else {
+                max - min
+            };
+            (min + range * t).rem_euclid(360.)
+        };
+
+        match self.space {
+            RandColorSpace::Hsv => {
+                let (min, max) = (Hsva::from(self.min), Hsva::from(self.max));
+                let t = channel(0., 1.);
+                Color::Hsva(Hsva::new(
+                    hue(min.hue, max.hue, t),
+                    channel(min.saturation, max.saturation),
+                    channel(min.value, max.value),
+                    channel(min.alpha, max.alpha),
+                ))
+            }
+            RandColorSpace::Oklch => {
+                let (min, max) = (Oklcha::from(self.min), Oklcha::from(self.max));
+                let t = channel(0., 1.);
+                Color::Oklcha(Oklcha::new(
+                    channel(min.lightness, max.lightness),
+                    channel(min.chroma, max.chroma),
+                    hue(min.hue, max.hue, t),
+                    channel(min.alpha, max.alpha),
+                ))
+            }
+        }
+    }
+
+    fn constant(value: Color) -> Self {
+        Self {
+            min: value,
+            max: value,
+            space: RandColorSpace::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize)]
+pub struct RandPitchYaw {
+    pub yaw: RandF32,
+    pub pitch: RandF32,
+}
+
+impl RandValue for RandPitchYaw {
+    type Out = PitchYaw;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> PitchYaw {
+        PitchYaw::new(self.yaw.generate_with(rng), self.pitch.generate_with(rng)).normalize()
+    }
+
+    fn constant(value: PitchYaw) -> Self {
+        Self {
+            yaw: RandF32::constant(value.y),
+            pitch: RandF32::constant(value.p),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rng::GlobalRng;
+
+    const SAMPLES: usize = 20000;
+
+    /// Angles from the axis of directions drawn with the given distribution
+    fn sample_angles(distribution: SpreadDistribution, spread: f32) -> Vec<f32> {
+        let value = RandVec3 {
+            magnitude: RandF32::constant(1.),
+            direction: Vec3::new(1., 2., -0.5).normalize(),
+            spread,
+            distribution,
+        };
+        let mut rng = GlobalRng::from_seed(1234);
+
+        (0..SAMPLES)
+            .map(|_| value.generate_with(&mut rng).angle_between(value.direction))
+            .collect()
+    }
+
+    fn fraction_below(angles: &[f32], angle: f32) -> f32 {
+        angles.iter().filter(|a| **a < angle).count() as f32 / angles.len() as f32
+    }
+
+    #[test]
+    fn uniform_angle_is_uniform_in_angle() {
+        let spread = 0.8;
+        let angles = sample_angles(SpreadDistribution::UniformAngle, spread);
+
+        assert!(angles.iter().all(|a| *a <= spread + 1e-3));
+        assert!((fraction_below(&angles, spread / 2.) - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn uniform_solid_angle_is_uniform_over_cap() {
+        let spread = 0.8;
+        let angles = sample_angles(SpreadDistribution::UniformSolidAngle, spread);
+
+        assert!(angles.iter().all(|a| *a <= spread + 1e-3));
+        // Fraction inside a smaller cap is the ratio of the caps' areas
+        for inner in [0.2, 0.4, 0.6] {
+            let expected = (1. - f32::cos(inner)) / (1. - spread.cos());
+            let measured = fraction_below(&angles, inner);
+            assert!(
+                (measured - expected).abs() < 0.02,
+                "Inside {inner}: expected {expected}, got {measured}"
+            );
+        }
+    }
+
+    #[test]
+    fn gaussian_has_expected_spread() {
+        let sigma = 0.1;
+        let angles = sample_angles(SpreadDistribution::Gaussian, sigma);
+
+        // Rayleigh distribution: P(angle < sigma) = 1 - exp(-1/2), mean = sigma * sqrt(PI/2)
+        let expected = 1. - (-0.5f32).exp();
+        assert!((fraction_below(&angles, sigma) - expected).abs() < 0.02);
+        let mean = angles.iter().sum::<f32>() / angles.len() as f32;
+        assert!((mean - sigma * (PI / 2.).sqrt()).abs() < 0.005);
+    }
+
+    #[test]
+    fn integer_ranges_are_inclusive() {
+        let value = RandI32 { min: -2, max: 2 };
+        let mut rng = GlobalRng::from_seed(5);
+        let values: Vec<i32> = (0..200).map(|_| value.generate_with(&mut rng)).collect();
+
+        assert!(values.iter().all(|v| (-2..=2).contains(v)));
+        assert!(values.contains(&-2) && values.contains(&2));
+    }
+
+    #[test]
+    fn bool_follows_probability() {
+        let value = RandBool { probability: 0.25 };
+        let mut rng = GlobalRng::from_seed(5);
+        let hits = (0..SAMPLES)
+            .filter(|_| value.generate_with(&mut rng))
+            .count();
+
+        assert!((hits as f32 / SAMPLES as f32 - 0.25).abs() < 0.02);
+        assert!(RandBool::constant(true).generate_with(&mut rng));
+    }
+
+    #[test]
+    fn quat_within_angle() {
+        let base = Quat::from_rotation_y(1.);
+        let value = RandQuat {
+            base,
+            max_angle: 0.5,
+        };
+        let mut rng = GlobalRng::from_seed(5);
+
+        for _ in 0..1000 {
+            let rotation = value.generate_with(&mut rng);
+            assert!(rotation.is_normalized());
+            assert!(rotation.angle_between(base) <= 0.5 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn uniform_quat_rotates_vectors_uniformly() {
+        let value = RandQuat::uniform();
+        let mut rng = GlobalRng::from_seed(5);
+
+        // A uniformly rotated vector is uniform on the sphere, so its mean is zero
+        let mean = (0..SAMPLES)
+            .map(|_| value.generate_with(&mut rng) * Vec3::Z)
+            .sum::<Vec3>()
+            / SAMPLES as f32;
+        assert!(mean.length() < 0.02, "{mean}");
+    }
+
+    #[test]
+    fn color_hue_wraps_around() {
+        let value = RandColor {
+            min: Color::hsv(330., 1., 1.),
+            max: Color::hsv(30., 1., 1.),
+            space: RandColorSpace::Hsv,
+        };
+        let mut rng = GlobalRng::from_seed(5);
+
+        for _ in 0..1000 {
+            let hue = Hsva::from(value.generate_with(&mut rng)).hue;
+            assert!(!(30.1..329.9).contains(&hue), "{hue}");
+        }
+    }
+
+    #[test]
+    fn duration_within_range() {
+        let value = RandDuration {
+            min: Duration::from_millis(500),
+            max: Duration::from_secs(2),
+        };
+        let mut rng = GlobalRng::from_seed(5);
+
+        for _ in 0..1000 {
+            let duration = value.generate_with(&mut rng);
+            assert!((value.min..=value.max).contains(&duration));
+        }
+    }
+
+    fn sample_f32(value: &impl RandValue<Out = f32>) -> Vec<f32> {
+        let mut rng = GlobalRng::from_seed(99);
+        (0..SAMPLES)
+            .map(|_| value.generate_with(&mut rng))
+            .collect()
+    }
+
+    fn mean(values: &[f32]) -> f32 {
+        values.iter().sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn normal_is_centered_and_clamped() {
+        let value =
+            RandF32::uniform(0., 10.).with_distribution(F32Distribution::Normal { std_dev: 2. });
+        let values = sample_f32(&value);
+
+        assert!(values.iter().all(|v| (0.0..=10.).contains(v)));
+        assert!((mean(&values) - 5.).abs() < 0.05);
+        // About 68% within one standard deviation
+        let within = values.iter().filter(|v| (**v - 5.).abs() < 2.).count();
+        assert!((within as f32 / SAMPLES as f32 - 0.6827).abs() < 0.02);
+    }
+
+    #[test]
+    fn triangular_mean() {
+        let value =
+            RandF32::uniform(0., 3.).with_distribution(F32Distribution::Triangular { mode: 0. });
+        let values = sample_f32(&value);
+
+        assert!(values.iter().all(|v| (0.0..=3.).contains(v)));
+        assert!((mean(&values) - 1.).abs() < 0.03);
+    }
+
+    #[test]
+    fn exponential_mean() {
+        let value = RandF32::uniform(1., 1000.)
+            .with_distribution(F32Distribution::Exponential { rate: 2. });
+        let values = sample_f32(&value);
+
+        assert!((mean(&values) - 1.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn log_uniform_median_is_geometric_mean() {
+        let value = RandF32::uniform(1., 100.).with_distribution(F32Distribution::LogUniform);
+        let values = sample_f32(&value);
+
+        let below = values.iter().filter(|v| **v < 10.).count();
+        assert!((below as f32 / SAMPLES as f32 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn curve_inverse_cdf() {
+        // Inverse CDF of a distribution with density 2x on [0, 1]
+        let samples = (0..=64).map(|i| (i as f32 / 64.).sqrt());
+        let curve = SampleAutoCurve::new(Interval::UNIT, samples).unwrap();
+        let value =
+            RandF32::uniform(0., 2.).with_distribution(F32Distribution::Curve(Arc::new(curve)));
+        let values = sample_f32(&value);
+
+        assert!((mean(&values) - 4. / 3.).abs() < 0.03);
+    }
+
+    #[test]
+    fn old_ron_format_still_loads() {
+        let value: RandF32 = ron::from_str("(min: 1.0, max: 2.0)").unwrap();
+        assert!(matches!(value.distribution, F32Distribution::Uniform));
+
+        let value: RandF32 =
+            ron::from_str("(min: 1.0, max: 2.0, distribution: Normal(std_dev: 0.5))").unwrap();
+        assert!(matches!(
+            value.distribution,
+            F32Distribution::Normal { std_dev: 0.5 }
+        ));
+    }
+}
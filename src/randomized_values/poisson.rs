@@ -0,0 +1,486 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::sampling::SampleShape;
+
+/// Default number of candidates tried around each point before giving up on it, as
+/// suggested by Bridson
+const DEFAULT_ATTEMPTS: u32 = 30;
+
+/// Region covered by [`PoissonDisk2d`], centered on the origin
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub enum PoissonDomain2d {
+    Rectangle(Rectangle),
+    Disk(Circle),
+}
+
+/// Region covered by [`PoissonDisk3d`], centered on the origin
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub enum PoissonDomain3d {
+    Cuboid(Cuboid),
+    Sphere(Sphere),
+}
+
+/// Random points with a minimum separation (Poisson-disk, or blue noise), using
+/// Bridson's algorithm. Good for scattering props, trees or enemies without clumps or
+/// overlaps.
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct PoissonDisk2d {
+    pub domain: PoissonDomain2d,
+    /// Minimum distance between points
+    pub radius: f32,
+    /// Candidates tried around each point before giving up on it. More attempts pack
+    /// points more tightly, at a higher cost
+    pub attempts: u32,
+}
+
+/// 3D version of [`PoissonDisk2d`]
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct PoissonDisk3d {
+    pub domain: PoissonDomain3d,
+    /// Minimum distance between points
+    pub radius: f32,
+    /// Candidates tried around each point before giving up on it
+    pub attempts: u32,
+}
+
+impl PoissonDisk2d {
+    pub fn new(domain: PoissonDomain2d, radius: f32) -> Self {
+        Self {
+            domain,
+            radius,
+            attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Vec2> {
+        self.sample_variable(rng, self.radius, |_| Some(self.radius))
+    }
+
+    /// Only keeps points where `mask` returns `true`
+    pub fn sample_masked<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        mask: impl Fn(Vec2) -> bool,
+    ) -> Vec<Vec2> {
+        self.sample_variable(rng, self.radius, |point| mask(point).then_some(self.radius))
+    }
+
+    /// Spreads points according to `density`, from 0 (no points) to 1 (points
+    /// `radius` apart). Lower densities space points further apart.
+    ///
+    /// Densities are clamped to `min_density` and above, except for zero, as very low
+    /// densities need a very large search around each point.
+    pub fn sample_with_density<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        min_density: f32,
+        density: impl Fn(Vec2) -> f32,
+    ) -> Vec<Vec2> {
+        let min_density = min_density.clamp(f32::EPSILON, 1.);
+        let max_radius = self.radius / min_density.sqrt();
+
+        self.sample_variable(rng, max_radius, |point| {
+            let density = density(point).min(1.);
+            (density > 0.).then(|| self.radius / density.max(min_density).sqrt())
+        })
+    }
+
+    /// Each point keeps other points at least `radius_at(point)` away, clamped to
+    /// `[radius, max_radius]`. Points where `radius_at` returns `None` are rejected.
+    pub fn sample_variable<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        max_radius: f32,
+        radius_at: impl Fn(Vec2) -> Option<f32>,
+    ) -> Vec<Vec2> {
+        let (half_size, contains): (Vec2, Box<dyn Fn(Vec2) -> bool>) = match self.domain {
+            PoissonDomain2d::Rectangle(rectangle) => (
+                rectangle.half_size,
+                Box::new(move |p: Vec2| p.abs().cmple(rectangle.half_size).all()),
+            ),
+            PoissonDomain2d::Disk(circle) => (
+                Vec2::splat(circle.radius),
+                Box::new(move |p: Vec2| p.length() <= circle.radius),
+            ),
+        };
+        let domain = self.domain;
+
+        bridson(
+            Domain {
+                min: -half_size,
+                max: half_size,
+                contains,
+                sample: |rng: &mut R| match domain {
+                    PoissonDomain2d::Rectangle(rectangle) => rectangle.random_interior(rng),
+                    PoissonDomain2d::Disk(circle) => circle.random_interior(rng),
+                },
+            },
+            self.radius,
+            max_radius,
+            self.attempts,
+            radius_at,
+            rng,
+        )
+    }
+}
+
+impl PoissonDisk3d {
+    pub fn new(domain: PoissonDomain3d, radius: f32) -> Self {
+        Self {
+            domain,
+            radius,
+            attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Vec3> {
+        self.sample_variable(rng, self.radius, |_| Some(self.radius))
+    }
+
+    /// Only keeps points where `mask` returns `true`
+    pub fn sample_masked<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        mask: impl Fn(Vec3) -> bool,
+    ) -> Vec<Vec3> {
+        self.sample_variable(rng, self.radius, |point| mask(point).then_some(self.radius))
+    }
+
+    /// See [`PoissonDisk2d::sample_with_density`]
+    pub fn sample_with_density<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        min_density: f32,
+        density: impl Fn(Vec3) -> f32,
+    ) -> Vec<Vec3> {
+        let min_density = min_density.clamp(f32::EPSILON, 1.);
+        let max_radius = self.radius / min_density.cbrt();
+
+        self.sample_variable(rng, max_radius, |point| {
+            let density = density(point).min(1.);
+            (density > 0.).then(|| self.radius / density.max(min_density).cbrt())
+        })
+    }
+
+    /// See [`PoissonDisk2d::sample_variable`]
+    pub fn sample_variable<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        max_radius: f32,
+        radius_at: impl Fn(Vec3) -> Option<f32>,
+    ) -> Vec<Vec3> {
+        let (half_size, contains): (Vec3, Box<dyn Fn(Vec3) -> bool>) = match self.domain {
+            PoissonDomain3d::Cuboid(cuboid) => (
+                cuboid.half_size,
+                Box::new(move |p: Vec3| p.abs().cmple(cuboid.half_size).all()),
+            ),
+            PoissonDomain3d::Sphere(sphere) => (
+                Vec3::splat(sphere.radius),
+                Box::new(move |p: Vec3| p.length() <= sphere.radius),
+            ),
+        };
+        let domain = self.domain;
+
+        bridson(
+            Domain {
+                min: -half_size,
+                max: half_size,
+                contains,
+                sample: |rng: &mut R| match domain {
+                    PoissonDomain3d::Cuboid(cuboid) => cuboid.random_interior(rng),
+                    PoissonDomain3d::Sphere(sphere) => sphere.random_interior(rng),
+                },
+            },
+            self.radius,
+            max_radius,
+            self.attempts,
+            radius_at,
+            rng,
+        )
+    }
+}
+
+/// Points the sampler can work with, in 2D or 3D
+trait GridPoint: Copy {
+    const DIMENSIONS: f32;
+
+    fn distance(self, other: Self) -> f32;
+    /// Grid coordinates of the point, with a zero third coordinate in 2D
+    fn cell(self, origin: Self, cell_size: f32) -> IVec3;
+    /// Uniform random point at a distance between `radius` and `2 * radius`
+    fn random_around<R: Rng + ?Sized>(self, radius: f32, rng: &mut R) -> Self;
+}
+
+impl GridPoint for Vec2 {
+    const DIMENSIONS: f32 = 2.;
+
+    fn distance(self, other: Self) -> f32 {
+        Vec2::distance(self, other)
+    }
+
+    fn cell(self, origin: Self, cell_size: f32) -> IVec3 {
+        ((self - origin) / cell_size).floor().as_ivec2().extend(0)
+    }
+
+    fn random_around<R: Rng + ?Sized>(self, radius: f32, rng: &mut R) -> Self {
+        let angle = rng.random::<f32>() * 2. * PI;
+        let distance = radius * (1. + 3. * rng.random::<f32>()).sqrt();
+        self + Vec2::from_angle(angle) * distance
+    }
+}
+
+impl GridPoint for Vec3 {
+    const DIMENSIONS: f32 = 3.;
+
+    fn distance(self, other: Self) -> f32 {
+        Vec3::distance(self, other)
+    }
+
+    fn cell(self, origin: Self, cell_size: f32) -> IVec3 {
+        ((self - origin) / cell_size).floor().as_ivec3()
+    }
+
+    fn random_around<R: Rng + ?Sized>(self, radius: f32, rng: &mut R) -> Self {
+        let direction = Sphere::new(1.).random_boundary(rng);
+        let distance = radius * (1. + 7. * rng.random::<f32>()).cbrt();
+        self + direction * distance
+    }
+}
+
+/// Axis-aligned bounds of the sampled region, with a finer test of whether points are
+/// inside it and a way to pick points in it uniformly
+struct Domain<P, C, S> {
+    min: P,
+    max: P,
+    contains: C,
+    sample: S,
+}
+
+/// Accepted points, indexed by a background grid with cells small enough to hold at
+/// most one point
+struct Samples<P> {
+    origin: P,
+    cell_size: f32,
+    /// How many cells away the furthest conflicting point can be
+    reach: IVec3,
+    size: IVec3,
+    cells: Vec<Option<usize>>,
+    points: Vec<P>,
+    radii: Vec<f32>,
+}
+
+impl<P: GridPoint> Samples<P> {
+    fn new(min: P, max: P, min_radius: f32, max_radius: f32) -> Self {
+        let cell_size = min_radius / P::DIMENSIONS.sqrt();
+        let size = max.cell(min, cell_size) + 1;
+        let reach = (max_radius / cell_size).ceil() as i32;
+        let reach = if P::DIMENSIONS == 2. {
+            IVec3::new(reach, reach, 0)
+        } else {
+            IVec3::splat(reach)
+        };
+
+        Self {
+            origin: min,
+            cell_size,
+            reach,
+            size,
+            cells: vec![None; (size.x * size.y * size.z) as usize],
+            points: Vec::new(),
+            radii: Vec::new(),
+        }
+    }
+
+    fn index(&self, cell: IVec3) -> Option<usize> {
+        if cell.cmplt(IVec3::ZERO).any() || cell.cmpge(self.size).any() {
+            return None;
+        }
+        Some((cell.x + self.size.x * (cell.y + self.size.y * cell.z)) as usize)
+    }
+
+    /// Whether `point` is far enough from all other points, given its own radius
+    fn fits(&self, point: P, radius: f32) -> bool {
+        let cell = point.cell(self.origin, self.cell_size);
+        for z in -self.reach.z..=self.reach.z {
+            for y in -self.reach.y..=self.reach.y {
+                for x in -self.reach.x..=self.reach.x {
+                    let Some(index) = self.index(cell + IVec3::new(x, y, z)) else {
+                        continue;
+                    };
+                    if let Some(other) = self.cells[index]
+                        && point.distance(self.points[other]) < radius.max(self.radii[other])
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn insert(&mut self, point: P, radius: f32) -> usize {
+        let index = self.points.len();
+        if let Some(cell) = self.index(point.cell(self.origin, self.cell_size)) {
+            self.cells[cell] = Some(index);
+        }
+        self.points.push(point);
+        self.radii.push(radius);
+        index
+    }
+}
+
+fn bridson<P, R, C, S>(
+    domain: Domain<P, C, S>,
+    min_radius: f32,
+    max_radius: f32,
+    attempts: u32,
+    radius_at: impl Fn(P) -> Option<f32>,
+    rng: &mut R,
+) -> Vec<P>
+where
+    P: GridPoint,
+    R: Rng + ?Sized,
+    C: Fn(P) -> bool,
+    S: Fn(&mut R) -> P,
+{
+    if min_radius <= 0. {
+        return Vec::new();
+    }
+    let max_radius = max_radius.max(min_radius);
+    let radius_at = |point: P| {
+        radius_at(point)
+            .filter(|_| (domain.contains)(point))
+            .map(|radius| radius.clamp(min_radius, max_radius))
+    };
+
+    let mut samples = Samples::new(domain.min, domain.max, min_radius, max_radius);
+    let mut active: Vec<usize> = Vec::new();
+
+    loop {
+        // Seed a new point, which also picks up regions cut off from the previous
+        // ones by a mask
+        let seed = (0..attempts).find_map(|_| {
+            let point = (domain.sample)(rng);
+            let radius = radius_at(point)?;
+            samples.fits(point, radius).then_some((point, radius))
+        });
+        let Some((point, radius)) = seed else {
+            break;
+        };
+        active.push(samples.insert(point, radius));
+
+        while !active.is_empty() {
+            let slot = rng.random_range(0..active.len());
+            let index = active[slot];
+            let (around, around_radius) = (samples.points[index], samples.radii[index]);
+
+            let candidate = (0..attempts).find_map(|_| {
+                let point = around.random_around(around_radius, rng);
+                let radius = radius_at(point)?;
+                samples.fits(point, radius).then_some((point, radius))
+            });
+
+            match candidate {
+                Some((point, radius)) => active.push(samples.insert(point, radius)),
+                None => {
+                    active.swap_remove(slot);
+                }
+            }
+        }
+    }
+
+    samples.points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::rng::GlobalRng;
+
+    fn min_separation<P: GridPoint>(points: &[P]) -> f32 {
+        let mut min = f32::INFINITY;
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                min = min.min(a.distance(*b));
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn rectangle_keeps_minimum_distance() {
+        let sampler = PoissonDisk2d::new(PoissonDomain2d::Rectangle(Rectangle::new(20., 10.)), 1.);
+        let points = sampler.sample(&mut GlobalRng::from_seed(1));
+
+        assert!(min_separation(&points) >= 1.);
+        assert!(points.iter().all(|p| p.x.abs() <= 10. && p.y.abs() <= 5.));
+        // A maximal packing covers the area well: each point takes up less than
+        // a disk of radius 1
+        assert!(points.len() as f32 > 200. / PI, "{}", points.len());
+    }
+
+    #[test]
+    fn disk_and_mask() {
+        let sampler = PoissonDisk2d::new(PoissonDomain2d::Disk(Circle::new(8.)), 0.5);
+        // Two separate regions, both should get points
+        let points = sampler.sample_masked(&mut GlobalRng::from_seed(1), |p| p.x.abs() > 4.);
+
+        assert!(min_separation(&points) >= 0.5);
+        assert!(points.iter().all(|p| p.length() <= 8. && p.x.abs() > 4.));
+        assert!(points.iter().any(|p| p.x > 0.) && points.iter().any(|p| p.x < 0.));
+    }
+
+    #[test]
+    fn variable_radius_respects_both_radii() {
+        let sampler = PoissonDisk2d::new(PoissonDomain2d::Rectangle(Rectangle::new(20., 20.)), 0.5);
+        let radius_at = |p: Vec2| Some(if p.x < 0. { 0.5 } else { 2. });
+        let points = sampler.sample_variable(&mut GlobalRng::from_seed(1), 2., radius_at);
+
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                let required = radius_at(*a).unwrap().max(radius_at(*b).unwrap());
+                assert!(a.distance(*b) >= required);
+            }
+        }
+        let left = points.iter().filter(|p| p.x < 0.).count();
+        assert!(left > 4 * (points.len() - left));
+    }
+
+    #[test]
+    fn density_spreads_points() {
+        let sampler = PoissonDisk2d::new(PoissonDomain2d::Rectangle(Rectangle::new(20., 20.)), 0.5);
+        let points = sampler.sample_with_density(&mut GlobalRng::from_seed(1), 0.05, |p| {
+            if p.y > 0. { 1. } else { 0.1 }
+        });
+
+        assert!(min_separation(&points) >= 0.5);
+        let dense = points.iter().filter(|p| p.y > 0.).count();
+        assert!(dense > 5 * (points.len() - dense));
+    }
+
+    #[test]
+    fn same_seed_same_points() {
+        let sampler = PoissonDisk3d::new(PoissonDomain3d::Sphere(Sphere::new(3.)), 1.);
+
+        let a = sampler.sample(&mut GlobalRng::from_seed(9));
+        let b = sampler.sample(&mut GlobalRng::from_seed(9));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn cuboid_keeps_minimum_distance() {
+        let sampler = PoissonDisk3d::new(PoissonDomain3d::Cuboid(Cuboid::new(6., 4., 6.)), 1.);
+        let points = sampler.sample(&mut GlobalRng::from_seed(1));
+
+        assert!(min_separation(&points) >= 1.);
+        assert!(
+            points
+                .iter()
+                .all(|p| p.abs().cmple(Vec3::new(3., 2., 3.)).all())
+        );
+        assert!(points.len() > 50, "{}", points.len());
+    }
+}
@@ -0,0 +1,362 @@
+use bevy::math::{
+    Vec2, Vec3,
+    primitives::{
+        Annulus, Capsule2d, Capsule3d, Circle, Cuboid, Cylinder, Rectangle, Sphere, Triangle2d,
+        Triangle3d,
+    },
+};
+use rand::Rng;
+use std::{
+    f32::consts::{PI, TAU},
+    ops::{Add, Mul},
+};
+
+/// Uniform sampling of Bevy's primitives, like Bevy's `ShapeSample` but using this
+/// crate's version of `rand`
+pub(crate) trait SampleShape {
+    type Output;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Output;
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Output;
+}
+
+/// Uniform value in `[-1, 1)`
+fn signed_unit<R: Rng + ?Sized>(rng: &mut R) -> f32 {
+    rng.random::<f32>() * 2. - 1.
+}
+
+fn random_sign<R: Rng + ?Sized>(rng: &mut R) -> f32 {
+    if rng.random() { 1. } else { -1. }
+}
+
+fn unit_circle<R: Rng + ?Sized>(rng: &mut R) -> Vec2 {
+    Vec2::from_angle(rng.random::<f32>() * TAU)
+}
+
+fn unit_sphere<R: Rng + ?Sized>(rng: &mut R) -> Vec3 {
+    let z = signed_unit(rng);
+    let xy = unit_circle(rng) * (1. - z * z).max(0.).sqrt();
+    Vec3::new(xy.x, xy.y, z)
+}
+
+/// Index of one of the parts of a shape, picked with probability proportional to its
+/// weight (its length, area or volume)
+fn pick_weighted<R: Rng + ?Sized>(weights: &[f32], rng: &mut R) -> usize {
+    let mut value = rng.random::<f32>() * weights.iter().sum::<f32>();
+    for (index, weight) in weights.iter().enumerate() {
+        if value < *weight {
+            return index;
+        }
+        value -= weight;
+    }
+    weights.len() - 1
+}
+
+fn triangle_interior<V, R>(vertices: [V; 3], rng: &mut R) -> V
+where
+    V: Copy + Add<Output = V> + Mul<f32, Output = V>,
+    R: Rng + ?Sized,
+{
+    let (mut u, mut v) = (rng.random::<f32>(), rng.random::<f32>());
+    // Fold the far half of the parallelogram back onto the triangle
+    if u + v > 1. {
+        (u, v) = (1. - u, 1. - v);
+    }
+    let [a, b, c] = vertices;
+    a * (1. - u - v) + b * u + c * v
+}
+
+fn triangle_boundary<V, R>(vertices: [V; 3], lengths: [f32; 3], rng: &mut R) -> V
+where
+    V: Copy + Add<Output = V> + Mul<f32, Output = V>,
+    R: Rng + ?Sized,
+{
+    let edge = pick_weighted(&lengths, rng);
+    let t = rng.random::<f32>();
+    vertices[edge] * (1. - t) + vertices[(edge + 1) % 3] * t
+}
+
+impl SampleShape for Circle {
+    type Output = Vec2;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        unit_circle(rng) * self.radius * rng.random::<f32>().sqrt()
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        unit_circle(rng) * self.radius
+    }
+}
+
+impl SampleShape for Annulus {
+    type Output = Vec2;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let inner = self.inner_circle.radius.powi(2);
+        let outer = self.outer_circle.radius.powi(2);
+        unit_circle(rng) * (inner + (outer - inner) * rng.random::<f32>()).sqrt()
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let radii = [self.outer_circle.radius, self.inner_circle.radius];
+        unit_circle(rng) * radii[pick_weighted(&radii, rng)]
+    }
+}
+
+impl SampleShape for Rectangle {
+    type Output = Vec2;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        Vec2::new(signed_unit(rng), signed_unit(rng)) * self.half_size
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let mut point = self.random_interior(rng);
+        // Pick a pair of opposite sides by their length, then one of the two
+        let axis = 1 - pick_weighted(&self.half_size.to_array(), rng);
+        point[axis] = random_sign(rng) * self.half_size[axis];
+        point
+    }
+}
+
+impl SampleShape for Triangle2d {
+    type Output = Vec2;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        triangle_interior(self.vertices, rng)
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let [a, b, c] = self.vertices;
+        triangle_boundary(
+            self.vertices,
+            [a.distance(b), b.distance(c), c.distance(a)],
+            rng,
+        )
+    }
+}
+
+impl SampleShape for Capsule2d {
+    type Output = Vec2;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let rectangle_area = 4. * self.radius * self.half_length;
+        let disk_area = PI * self.radius * self.radius;
+
+        if pick_weighted(&[rectangle_area, disk_area], rng) == 0 {
+            Vec2::new(self.radius, self.half_length) * Vec2::new(signed_unit(rng), signed_unit(rng))
+        } else {
+            // Each half of the disk goes to the matching end
+            let point = Circle::new(self.radius).random_interior(rng);
+            point + Vec2::Y * self.half_length.copysign(point.y)
+        }
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let sides_length = 4. * self.half_length;
+        let circle_length = TAU * self.radius;
+
+        if pick_weighted(&[sides_length, circle_length], rng) == 0 {
+            Vec2::new(
+                random_sign(rng) * self.radius,
+                signed_unit(rng) * self.half_length,
+            )
+        } else {
+            let point = Circle::new(self.radius).random_boundary(rng);
+            point + Vec2::Y * self.half_length.copysign(point.y)
+        }
+    }
+}
+
+impl SampleShape for Sphere {
+    type Output = Vec3;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        unit_sphere(rng) * self.radius * rng.random::<f32>().cbrt()
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        unit_sphere(rng) * self.radius
+    }
+}
+
+impl SampleShape for Cuboid {
+    type Output = Vec3;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        Vec3::new(signed_unit(rng), signed_unit(rng), signed_unit(rng)) * self.half_size
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let mut point = self.random_interior(rng);
+        let Vec3 { x, y, z } = self.half_size;
+        // Pick a pair of opposite faces by their area, then one of the two
+        let axis = pick_weighted(&[y * z, x * z, x * y], rng);
+        point[axis] = random_sign(rng) * self.half_size[axis];
+        point
+    }
+}
+
+impl SampleShape for Cylinder {
+    type Output = Vec3;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let disk = Circle::new(self.radius).random_interior(rng);
+        Vec3::new(disk.x, signed_unit(rng) * self.half_height, disk.y)
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        // Caps and side areas, divided by the circumference
+        if pick_weighted(&[self.radius, 2. * self.half_height], rng) == 0 {
+            let disk = Circle::new(self.radius).random_interior(rng);
+            Vec3::new(disk.x, random_sign(rng) * self.half_height, disk.y)
+        } else {
+            let circle = Circle::new(self.radius).random_boundary(rng);
+            Vec3::new(circle.x, signed_unit(rng) * self.half_height, circle.y)
+        }
+    }
+}
+
+impl SampleShape for Capsule3d {
+    type Output = Vec3;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let cylinder = Cylinder::new(self.radius, 2. * self.half_length);
+        // Cylinder and sphere volumes, divided by the area of a disk
+        let weights = [2. * self.half_length, 4. / 3. * self.radius];
+
+        if pick_weighted(&weights, rng) == 0 {
+            cylinder.random_interior(rng)
+        } else {
+            let point = Sphere::new(self.radius).random_interior(rng);
+            point + Vec3::Y * self.half_length.copysign(point.y)
+        }
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        // Side and sphere areas, divided by the circumference
+        if pick_weighted(&[self.half_length, self.radius], rng) == 0 {
+            let circle = Circle::new(self.radius).random_boundary(rng);
+            Vec3::new(circle.x, signed_unit(rng) * self.half_length, circle.y)
+        } else {
+            let point = Sphere::new(self.radius).random_boundary(rng);
+            point + Vec3::Y * self.half_length.copysign(point.y)
+        }
+    }
+}
+
+impl SampleShape for Triangle3d {
+    type Output = Vec3;
+
+    fn random_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        triangle_interior(self.vertices, rng)
+    }
+
+    fn random_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let [a, b, c] = self.vertices;
+        triangle_boundary(
+            self.vertices,
+            [a.distance(b), b.distance(c), c.distance(a)],
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::rng::GlobalRng;
+
+    const SAMPLES: usize = 5000;
+    const EPSILON: f32 = 0.001;
+
+    fn samples<S: SampleShape>(shape: &S, boundary: bool) -> Vec<S::Output> {
+        let mut rng = GlobalRng::from_seed(8);
+        (0..SAMPLES)
+            .map(|_| match boundary {
+                false => shape.random_interior(&mut rng),
+                true => shape.random_boundary(&mut rng),
+            })
+            .collect()
+    }
+
+    fn fraction<T>(points: &[T], filter: impl Fn(&T) -> bool) -> f32 {
+        points.iter().filter(|point| filter(point)).count() as f32 / points.len() as f32
+    }
+
+    #[test]
+    fn disk_interior_is_uniform_by_area() {
+        let points = samples(&Circle::new(2.), false);
+
+        assert!(points.iter().all(|p| p.length() <= 2. + EPSILON));
+        let inner = fraction(&points, |p| p.length() < 1.);
+        assert!((inner - 0.25).abs() < 0.02, "{inner}");
+    }
+
+    #[test]
+    fn rectangle_boundary_is_weighted_by_side_length() {
+        let rectangle = Rectangle::new(6., 2.);
+        let points = samples(&rectangle, true);
+
+        for point in &points {
+            let on_x = (point.x.abs() - 3.).abs() < EPSILON;
+            let on_y = (point.y.abs() - 1.).abs() < EPSILON;
+            assert!(on_x || on_y, "{point}");
+        }
+        let long_sides = fraction(&points, |p| (p.y.abs() - 1.).abs() < EPSILON);
+        assert!((long_sides - 0.75).abs() < 0.02, "{long_sides}");
+    }
+
+    #[test]
+    fn sphere_interior_is_uniform_by_volume() {
+        let points = samples(&Sphere::new(1.), false);
+
+        assert!(points.iter().all(|p| p.length() <= 1. + EPSILON));
+        let inner = fraction(&points, |p| p.length() < 0.5);
+        assert!((inner - 0.125).abs() < 0.02, "{inner}");
+        let upper = fraction(&points, |p| p.y > 0.);
+        assert!((upper - 0.5).abs() < 0.03, "{upper}");
+    }
+
+    #[test]
+    fn cuboid_boundary_lies_on_faces() {
+        let cuboid = Cuboid::new(1., 2., 3.);
+
+        for point in samples(&cuboid, true) {
+            let inside = point.abs().cmple(cuboid.half_size + EPSILON).all();
+            let on_face = (point.abs() - cuboid.half_size)
+                .abs()
+                .cmplt(Vec3::splat(EPSILON));
+            assert!(inside && on_face.any(), "{point}");
+        }
+    }
+
+    #[test]
+    fn capsule_splits_between_body_and_caps() {
+        let capsule = Capsule3d::new(1., 2.);
+        let points = samples(&capsule, false);
+
+        for point in &points {
+            let axis = Vec3::Y * point.y.clamp(-1., 1.);
+            assert!(point.distance(axis) <= 1. + EPSILON, "{point}");
+        }
+        // The cylinder holds 2 / (2 + 4 / 3) of the volume
+        let body = fraction(&points, |p| p.y.abs() <= 1.);
+        assert!((body - 0.6).abs() < 0.02, "{body}");
+
+        for point in samples(&capsule, true) {
+            let axis = Vec3::Y * point.y.clamp(-1., 1.);
+            assert!((point.distance(axis) - 1.).abs() < EPSILON, "{point}");
+        }
+    }
+
+    #[test]
+    fn triangle_interior_stays_inside() {
+        let triangle = Triangle2d::new(Vec2::ZERO, Vec2::X * 2., Vec2::Y * 2.);
+
+        for point in samples(&triangle, false) {
+            assert!(point.x >= -EPSILON && point.y >= -EPSILON);
+            assert!(point.x + point.y <= 2. + EPSILON, "{point}");
+        }
+    }
+}
@@ -0,0 +1,460 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::{RandValue, sampling::SampleShape};
+
+/// Whether shape samplers pick points inside the shape or on its surface
+#[derive(Debug, Clone, Copy, Reflect, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SampleRegion {
+    #[default]
+    Interior,
+    Boundary,
+}
+
+/// 2D shapes for [`RandPoint2`], centered on the origin
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum PointShape2d {
+    Point,
+    Disk(Circle),
+    Annulus(Annulus),
+    Rectangle(Rectangle),
+    Triangle(Triangle2d),
+    Capsule(Capsule2d),
+}
+
+/// Random point in or on a 2D shape
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RandPoint2 {
+    pub shape: PointShape2d,
+    #[serde(default)]
+    pub region: SampleRegion,
+    /// Added to every sampled point
+    #[serde(default)]
+    pub offset: Vec2,
+}
+
+impl RandPoint2 {
+    pub fn new(shape: PointShape2d, region: SampleRegion) -> Self {
+        Self {
+            shape,
+            region,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+fn sample_shape<S: SampleShape, R: Rng + ?Sized>(
+    shape: &S,
+    region: SampleRegion,
+    rng: &mut R,
+) -> S::Output {
+    match region {
+        SampleRegion::Interior => shape.random_interior(rng),
+        SampleRegion::Boundary => shape.random_boundary(rng),
+    }
+}
+
+impl RandValue for RandPoint2 {
+    type Out = Vec2;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let region = self.region;
+        let point = match &self.shape {
+            PointShape2d::Point => Vec2::ZERO,
+            PointShape2d::Disk(circle) => sample_shape(circle, region, rng),
+            PointShape2d::Annulus(annulus) => sample_shape(annulus, region, rng),
+            PointShape2d::Rectangle(rectangle) => sample_shape(rectangle, region, rng),
+            PointShape2d::Triangle(triangle) => sample_shape(triangle, region, rng),
+            PointShape2d::Capsule(capsule) => sample_shape(capsule, region, rng),
+        };
+
+        point + self.offset
+    }
+
+    fn constant(value: Vec2) -> Self {
+        Self {
+            shape: PointShape2d::Point,
+            region: SampleRegion::Interior,
+            offset: value,
+        }
+    }
+}
+
+/// 3D shapes for [`RandPoint3`], centered on the origin and oriented along the Y axis
+/// like Bevy's primitives
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum PointShape3d {
+    Point,
+    Sphere(Sphere),
+    /// Upper (+Y) half of a sphere. Its boundary is the dome, without the flat base
+    Hemisphere(Sphere),
+    Cuboid(Cuboid),
+    Cylinder(Cylinder),
+    Capsule(Capsule3d),
+    /// Apex towards +Y
+    Cone(Cone),
+    /// 2D shape lying on the XZ plane, with its X axis along X and its Y axis along -Z
+    Flat(PointShape2d),
+    /// Surface of a triangle mesh. The region is ignored
+    Mesh(MeshSurface),
+    /// Curve through control points. The region is ignored
+    Path(SplinePath),
+}
+
+/// Random point in or on a 3D shape, e.g. spawn positions
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RandPoint3 {
+    pub shape: PointShape3d,
+    #[serde(default)]
+    pub region: SampleRegion,
+    /// Applied to every sampled point
+    #[serde(default)]
+    pub isometry: Isometry3d,
+}
+
+impl RandPoint3 {
+    pub fn new(shape: PointShape3d, region: SampleRegion) -> Self {
+        Self {
+            shape,
+            region,
+            isometry: Isometry3d::IDENTITY,
+        }
+    }
+
+    pub fn with_isometry(self, isometry: Isometry3d) -> Self {
+        Self { isometry, ..self }
+    }
+}
+
+impl RandValue for RandPoint3 {
+    type Out = Vec3;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let region = self.region;
+        let point = match &self.shape {
+            PointShape3d::Point => Vec3::ZERO,
+            PointShape3d::Sphere(sphere) => sample_shape(sphere, region, rng),
+            PointShape3d::Hemisphere(sphere) => {
+                let point = sample_shape(sphere, region, rng);
+                point.with_y(point.y.abs())
+            }
+            PointShape3d::Cuboid(cuboid) => sample_shape(cuboid, region, rng),
+            PointShape3d::Cylinder(cylinder) => sample_shape(cylinder, region, rng),
+            PointShape3d::Capsule(capsule) => sample_shape(capsule, region, rng),
+            PointShape3d::Cone(cone) => sample_cone(cone, region, rng),
+            PointShape3d::Flat(shape) => {
+                let point = RandPoint2::new(shape.clone(), region).generate_with(rng);
+                Vec3::new(point.x, 0., -point.y)
+            }
+            PointShape3d::Mesh(mesh) => mesh.sample(rng).unwrap_or_default(),
+            PointShape3d::Path(path) => path.sample(rng).unwrap_or_default(),
+        };
+
+        self.isometry.transform_point(point).into()
+    }
+
+    fn constant(value: Vec3) -> Self {
+        Self::new(PointShape3d::Point, SampleRegion::Interior)
+            .with_isometry(Isometry3d::from_translation(value))
+    }
+}
+
+/// Uniform point on a disk of the given radius in the XZ plane
+fn sample_disk_xz<R: Rng + ?Sized>(radius: f32, rng: &mut R) -> Vec3 {
+    let point = Circle::new(radius).random_interior(rng);
+    Vec3::new(point.x, 0., point.y)
+}
+
+fn sample_cone<R: Rng + ?Sized>(cone: &Cone, region: SampleRegion, rng: &mut R) -> Vec3 {
+    let apex = Vec3::Y * (cone.height / 2.);
+    let down = Vec3::NEG_Y * cone.height;
+
+    match region {
+        SampleRegion::Interior => {
+            // Area of the cross-section grows with the square of the distance to the apex
+            let t = rng.random::<f32>().cbrt();
+            apex + down * t + sample_disk_xz(cone.radius * t, rng)
+        }
+        SampleRegion::Boundary => {
+            let base_area = PI * cone.radius * cone.radius;
+            let lateral_area = PI * cone.radius * cone.radius.hypot(cone.height);
+
+            if rng.random::<f32>() * (base_area + lateral_area) < base_area {
+                apex + down + sample_disk_xz(cone.radius, rng)
+            } else {
+                // Circumference grows linearly with the distance to the apex
+                let t = rng.random::<f32>().sqrt();
+                let angle = rng.random::<f32>() * 2. * PI;
+                let radius = cone.radius * t;
+                apex + down * t + Vec3::new(angle.cos() * radius, 0., angle.sin() * radius)
+            }
+        }
+    }
+}
+
+/// Index of the entry of a cumulative sum that contains `value`
+fn cumulative_index(cumulative: &[f32], value: f32) -> usize {
+    cumulative
+        .partition_point(|total| *total <= value)
+        .min(cumulative.len() - 1)
+}
+
+/// Triangles to sample points on, weighted by area
+#[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Triangle3d>", into = "Vec<Triangle3d>")]
+pub struct MeshSurface {
+    triangles: Vec<Triangle3d>,
+    /// Running total of the triangle areas
+    cumulative_areas: Vec<f32>,
+}
+
+impl MeshSurface {
+    pub fn new(triangles: Vec<Triangle3d>) -> Self {
+        let cumulative_areas = triangles
+            .iter()
+            .scan(0., |total, triangle| {
+                *total += triangle.area();
+                Some(*total)
+            })
+            .collect();
+
+        Self {
+            triangles,
+            cumulative_areas,
+        }
+    }
+
+    /// Surface of a triangle-list `Mesh`, or `None` if its triangles cannot be read
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        Some(Self::new(mesh.triangles().ok()?.collect()))
+    }
+
+    pub fn triangles(&self) -> &[Triangle3d] {
+        &self.triangles
+    }
+
+    pub fn area(&self) -> f32 {
+        self.cumulative_areas.last().copied().unwrap_or(0.)
+    }
+
+    /// Uniform point on the surface, or `None` if it has no area
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Vec3> {
+        if self.area() <= 0. {
+            return None;
+        }
+
+        let index = cumulative_index(&self.cumulative_areas, rng.random::<f32>() * self.area());
+        Some(self.triangles[index].random_interior(rng))
+    }
+}
+
+impl From<Vec<Triangle3d>> for MeshSurface {
+    fn from(triangles: Vec<Triangle3d>) -> Self {
+        Self::new(triangles)
+    }
+}
+
+impl From<MeshSurface> for Vec<Triangle3d> {
+    fn from(surface: MeshSurface) -> Self {
+        surface.triangles
+    }
+}
+
+/// Catmull-Rom spline going through all its points
+#[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Vec3>", into = "Vec<Vec3>")]
+pub struct SplinePath {
+    points: Vec<Vec3>,
+    /// Polyline approximating the curve, used to sample it uniformly by length
+    polyline: Vec<Vec3>,
+    cumulative_lengths: Vec<f32>,
+}
+
+/// Polyline segments per span between two control points
+const PATH_SUBDIVISIONS: usize = 16;
+
+impl SplinePath {
+    pub fn new(points: Vec<Vec3>) -> Self {
+        let polyline = match points.len() {
+            0 | 1 => points.clone(),
+            _ => {
+                let spans = points.len() - 1;
+                (0..=spans * PATH_SUBDIVISIONS)
+                    .map(|i| {
+                        let span = (i / PATH_SUBDIVISIONS).min(spans - 1);
+                        let t = (i - span * PATH_SUBDIVISIONS) as f32 / PATH_SUBDIVISIONS as f32;
+                        catmull_rom(&points, span, t)
+                    })
+                    .collect()
+            }
+        };
+        let cumulative_lengths = polyline
+            .windows(2)
+            .scan(0., |total, segment| {
+                *total += segment[0].distance(segment[1]);
+                Some(*total)
+            })
+            .collect();
+
+        Self {
+            points,
+            polyline,
+            cumulative_lengths,
+        }
+    }
+
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    /// Approximate length of the curve
+    pub fn length(&self) -> f32 {
+        self.cumulative_lengths.last().copied().unwrap_or(0.)
+    }
+
+    /// Point on the curve, uniformly distributed along its length. `None` if the path
+    /// has no points
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Vec3> {
+        if self.length() <= 0. {
+            return self.points.first().copied();
+        }
+
+        let distance = rng.random::<f32>() * self.length();
+        let index = cumulative_index(&self.cumulative_lengths, distance);
+        let start = if index == 0 {
+            0.
+        } else {
+            self.cumulative_lengths[index - 1]
+        };
+        let segment = self.cumulative_lengths[index] - start;
+        let t = if segment > 0. {
+            (distance - start) / segment
+        } else {
+            0.
+        };
+
+        Some(self.polyline[index].lerp(self.polyline[index + 1], t))
+    }
+}
+
+/// Point at `t` along the span from `points[span]` to `points[span + 1]`
+fn catmull_rom(points: &[Vec3], span: usize, t: f32) -> Vec3 {
+    let p1 = points[span];
+    let p2 = points[span + 1];
+    // Mirror the end points to get tangents at both ends of the path
+    let p0 = if span == 0 {
+        2. * p1 - p2
+    } else {
+        points[span - 1]
+    };
+    let p3 = points.get(span + 2).copied().unwrap_or(2. * p2 - p1);
+
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2. * p1)
+        + (p2 - p0) * t
+        + (2. * p0 - 5. * p1 + 4. * p2 - p3) * t2
+        + (3. * p1 - p0 - 3. * p2 + p3) * t3)
+}
+
+impl From<Vec<Vec3>> for SplinePath {
+    fn from(points: Vec<Vec3>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl From<SplinePath> for Vec<Vec3> {
+    fn from(path: SplinePath) -> Self {
+        path.points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::rng::GlobalRng;
+
+    const SAMPLES: usize = 5000;
+    const EPSILON: f32 = 0.001;
+
+    fn samples(value: &RandPoint3) -> Vec<Vec3> {
+        let mut rng = GlobalRng::from_seed(21);
+        (0..SAMPLES)
+            .map(|_| value.generate_with(&mut rng))
+            .collect()
+    }
+
+    #[test]
+    fn hemisphere_is_upper_half() {
+        let value = RandPoint3::new(
+            PointShape3d::Hemisphere(Sphere::new(2.)),
+            SampleRegion::Boundary,
+        );
+
+        for point in samples(&value) {
+            assert!(point.y >= 0.);
+            assert!((point.length() - 2.).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn cone_interior_is_denser_near_base() {
+        let cone = Cone {
+            radius: 1.,
+            height: 2.,
+        };
+        let value = RandPoint3::new(PointShape3d::Cone(cone), SampleRegion::Interior);
+        let points = samples(&value);
+
+        for point in &points {
+            // Radius allowed at this height
+            let t = (1. - point.y) / 2.;
+            assert!(Vec2::new(point.x, point.z).length() <= t + EPSILON);
+        }
+        // Only 1/8 of the volume is in the half closest to the apex
+        let upper = points.iter().filter(|p| p.y > 0.).count() as f32 / SAMPLES as f32;
+        assert!((upper - 0.125).abs() < 0.02, "{upper}");
+    }
+
+    #[test]
+    fn flat_annulus_lies_on_ground() {
+        let value = RandPoint3::new(
+            PointShape3d::Flat(PointShape2d::Annulus(Annulus::new(2., 3.))),
+            SampleRegion::Interior,
+        )
+        .with_isometry(Isometry3d::from_xyz(0., 5., 0.));
+
+        for point in samples(&value) {
+            assert_eq!(point.y, 5.);
+            let radius = Vec2::new(point.x, point.z).length();
+            assert!((2. - EPSILON..=3. + EPSILON).contains(&radius));
+        }
+    }
+
+    #[test]
+    fn mesh_surface_is_area_weighted() {
+        let small = Triangle3d::new(Vec3::ZERO, Vec3::X, Vec3::Y);
+        let large = Triangle3d::new(Vec3::Z, Vec3::Z + Vec3::X * 3., Vec3::Z + Vec3::Y * 3.);
+        let value = RandPoint3::new(
+            PointShape3d::Mesh(MeshSurface::new(vec![small, large])),
+            SampleRegion::Boundary,
+        );
+
+        let on_large = samples(&value).iter().filter(|p| p.z > 0.5).count();
+        assert!((on_large as f32 / SAMPLES as f32 - 0.9).abs() < 0.02);
+    }
+
+    #[test]
+    fn path_goes_through_points_and_is_uniform_by_length() {
+        let path = SplinePath::new(vec![Vec3::ZERO, Vec3::X, Vec3::X * 4.]);
+        assert!((path.length() - 4.).abs() < 0.01);
+
+        let value = RandPoint3::new(PointShape3d::Path(path), SampleRegion::Interior);
+        let points = samples(&value);
+        assert!(points.iter().all(|p| p.y == 0. && p.z == 0.));
+        let first_span = points.iter().filter(|p| p.x < 1.).count();
+        assert!((first_span as f32 / SAMPLES as f32 - 0.25).abs() < 0.02);
+    }
+}
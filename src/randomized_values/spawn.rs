@@ -0,0 +1,459 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+use super::{RandF32, RandQuat, RandValue, RandVec3, shapes::RandPoint3};
+
+/// Where a [`RandTransform`] places things
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum RandPosition {
+    /// Point in or on a shape
+    Shape(RandPoint3),
+    /// Offset from the origin, e.g. a random direction and distance
+    Offset(RandVec3),
+}
+
+/// How a [`RandTransform`] is rotated
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Serialize, Deserialize)]
+pub enum RandRotation {
+    Fixed(Quat),
+    /// Random rotation around the Y axis only, keeping things upright
+    Yaw,
+    /// Uniformly random orientation
+    Full,
+    /// Local Y along the surface normal, with a random twist around it. Unless given
+    /// explicitly (see [`RandTransform::generate_on_surface_with`]), the normal
+    /// points away from the position sampler's center, which suits round shapes.
+    AlignToNormal,
+}
+
+impl Default for RandRotation {
+    fn default() -> Self {
+        Self::Fixed(Quat::IDENTITY)
+    }
+}
+
+/// How a [`RandTransform`] is scaled
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum RandScale {
+    /// The same random factor on every axis
+    Uniform(RandF32),
+    PerAxis {
+        x: RandF32,
+        y: RandF32,
+        z: RandF32,
+    },
+}
+
+impl Default for RandScale {
+    fn default() -> Self {
+        Self::Uniform(RandF32::constant(1.))
+    }
+}
+
+/// Random [`Transform`], e.g. for scattering props or spawning enemies
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RandTransform {
+    pub position: RandPosition,
+    #[serde(default)]
+    pub rotation: RandRotation,
+    #[serde(default)]
+    pub scale: RandScale,
+}
+
+impl Default for RandTransform {
+    fn default() -> Self {
+        Self::constant(Transform::IDENTITY)
+    }
+}
+
+impl RandTransform {
+    pub fn new(position: RandPosition) -> Self {
+        Self {
+            position,
+            rotation: RandRotation::default(),
+            scale: RandScale::default(),
+        }
+    }
+
+    pub fn with_rotation(self, rotation: RandRotation) -> Self {
+        Self { rotation, ..self }
+    }
+
+    pub fn with_scale(self, scale: RandScale) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Like [`RandValue::generate_with`], but aligns to `normal` (e.g. from a raycast
+    /// hit) with [`RandRotation::AlignToNormal`]
+    pub fn generate_on_surface_with<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        normal: Vec3,
+    ) -> Transform {
+        let translation = self.generate_position(rng);
+        self.generate_transform(rng, translation, normal)
+    }
+
+    fn generate_position<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        match &self.position {
+            RandPosition::Shape(point) => point.generate_with(rng),
+            RandPosition::Offset(offset) => offset.generate_with(rng),
+        }
+    }
+
+    /// Direction from the position sampler's center to `translation`
+    fn outward_normal(&self, translation: Vec3) -> Vec3 {
+        let center = match &self.position {
+            RandPosition::Shape(point) => point.isometry.translation.into(),
+            RandPosition::Offset(_) => Vec3::ZERO,
+        };
+        (translation - center).normalize_or(Vec3::Y)
+    }
+
+    fn generate_transform<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        translation: Vec3,
+        normal: Vec3,
+    ) -> Transform {
+        let random_yaw = |rng: &mut R| Quat::from_rotation_y(rng.random_range(0.0..TAU));
+
+        let rotation = match self.rotation {
+            RandRotation::Fixed(rotation) => rotation,
+            RandRotation::Yaw => random_yaw(rng),
+            RandRotation::Full => RandQuat::uniform().generate_with(rng),
+            RandRotation::AlignToNormal => {
+                Quat::from_rotation_arc(Vec3::Y, normal.normalize_or(Vec3::Y)) * random_yaw(rng)
+            }
+        };
+
+        let scale = match &self.scale {
+            RandScale::Uniform(scale) => Vec3::splat(scale.generate_with(rng)),
+            RandScale::PerAxis { x, y, z } => Vec3::new(
+                x.generate_with(rng),
+                y.generate_with(rng),
+                z.generate_with(rng),
+            ),
+        };
+
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+}
+
+impl RandValue for RandTransform {
+    type Out = Transform;
+
+    fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Transform {
+        let translation = self.generate_position(rng);
+        let normal = self.outward_normal(translation);
+        self.generate_transform(rng, translation, normal)
+    }
+
+    fn constant(value: Transform) -> Self {
+        Self {
+            position: RandPosition::Shape(RandPoint3::constant(value.translation)),
+            rotation: RandRotation::Fixed(value.rotation),
+            scale: RandScale::PerAxis {
+                x: RandF32::constant(value.scale.x),
+                y: RandF32::constant(value.scale.y),
+                z: RandF32::constant(value.scale.z),
+            },
+        }
+    }
+}
+
+/// Layout of a batch of spawned things, around the origin. Rings and spirals lie in
+/// the XZ plane, starting towards -Z.
+#[derive(Debug, Clone, Reflect, PartialEq, Serialize, Deserialize)]
+pub enum SpawnPattern {
+    /// `counts` cells per axis, centered on the origin, each moved randomly by up to
+    /// `jitter` per axis
+    Grid {
+        counts: UVec3,
+        spacing: Vec3,
+        #[serde(default)]
+        jitter: Vec3,
+    },
+    Ring {
+        count: u32,
+        radius: f32,
+        /// Turns each transform's forward (-Z) away from the center
+        #[serde(default)]
+        face_outward: bool,
+    },
+    /// Spiral going from `start_radius` to `end_radius` over `turns` turns, rising by
+    /// `height` overall
+    Spiral {
+        count: u32,
+        start_radius: f32,
+        end_radius: f32,
+        turns: f32,
+        #[serde(default)]
+        height: f32,
+        /// Turns each transform's forward (-Z) away from the center
+        #[serde(default)]
+        face_outward: bool,
+    },
+    /// Evenly spaced from `start` to `end`, both included
+    Line { count: u32, start: Vec3, end: Vec3 },
+}
+
+impl SpawnPattern {
+    /// Transforms of the pattern's slots
+    pub fn transforms_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Transform> {
+        match *self {
+            SpawnPattern::Grid {
+                counts,
+                spacing,
+                jitter,
+            } => {
+                let center = (counts.max(UVec3::ONE) - UVec3::ONE).as_vec3() * spacing / 2.;
+                let mut jittered = |cell: UVec3| {
+                    let offset = Vec3::new(
+                        rng.random_range(-1.0..=1.),
+                        rng.random_range(-1.0..=1.),
+                        rng.random_range(-1.0..=1.),
+                    );
+                    Transform::from_translation(cell.as_vec3() * spacing - center + offset * jitter)
+                };
+
+                let mut transforms = Vec::with_capacity((counts.x * counts.y * counts.z) as usize);
+                for z in 0..counts.z {
+                    for y in 0..counts.y {
+                        for x in 0..counts.x {
+                            transforms.push(jittered(UVec3::new(x, y, z)));
+                        }
+                    }
+                }
+                transforms
+            }
+            SpawnPattern::Ring {
+                count,
+                radius,
+                face_outward,
+            } => (0..count)
+                .map(|index| {
+                    let yaw = TAU * index as f32 / count as f32;
+                    around_y(yaw, radius, 0., face_outward)
+                })
+                .collect(),
+            SpawnPattern::Spiral {
+                count,
+                start_radius,
+                end_radius,
+                turns,
+                height,
+                face_outward,
+            } => (0..count)
+                .map(|index| {
+                    let fraction = progress(index, count);
+                    around_y(
+                        TAU * turns * fraction,
+                        start_radius.lerp(end_radius, fraction),
+                        height * fraction,
+                        face_outward,
+                    )
+                })
+                .collect(),
+            SpawnPattern::Line { count, start, end } => (0..count)
+                .map(|index| Transform::from_translation(start.lerp(end, progress(index, count))))
+                .collect(),
+        }
+    }
+
+    /// Transforms of the pattern's slots, using the thread RNG
+    pub fn transforms(&self) -> Vec<Transform> {
+        self.transforms_with(&mut rand::rng())
+    }
+}
+
+/// Fraction of the way from the first to the last of `count` slots
+fn progress(index: u32, count: u32) -> f32 {
+    if count > 1 {
+        index as f32 / (count - 1) as f32
+    } else {
+        0.
+    }
+}
+
+/// Transform at `radius` from the Y axis towards `yaw`, with yaw 0 towards -Z
+fn around_y(yaw: f32, radius: f32, height: f32, face_outward: bool) -> Transform {
+    let rotation = Quat::from_rotation_y(yaw);
+    Transform {
+        translation: rotation * Vec3::NEG_Z * radius + Vec3::Y * height,
+        rotation: if face_outward {
+            rotation
+        } else {
+            Quat::IDENTITY
+        },
+        ..default()
+    }
+}
+
+/// Batch of random transforms laid out in a pattern, each varied by a
+/// [`RandTransform`] in its slot's local space. Can be defined entirely in data.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct Spawner {
+    pub pattern: SpawnPattern,
+    #[serde(default)]
+    pub variation: RandTransform,
+}
+
+impl Spawner {
+    pub fn new(pattern: SpawnPattern) -> Self {
+        Self {
+            pattern,
+            variation: RandTransform::default(),
+        }
+    }
+
+    pub fn with_variation(self, variation: RandTransform) -> Self {
+        Self { variation, ..self }
+    }
+
+    pub fn spawn_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Transform> {
+        self.pattern
+            .transforms_with(rng)
+            .into_iter()
+            .map(|slot| slot * self.variation.generate_with(rng))
+            .collect()
+    }
+
+    /// Spawns using the thread RNG
+    pub fn spawn(&self) -> Vec<Transform> {
+        self.spawn_with(&mut rand::rng())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomized_values::shapes::{PointShape3d, SampleRegion};
+
+    #[test]
+    fn align_to_normal_points_up_along_normal() {
+        let value = RandTransform::new(RandPosition::Shape(RandPoint3::new(
+            PointShape3d::Sphere(Sphere::new(2.)),
+            SampleRegion::Boundary,
+        )))
+        .with_rotation(RandRotation::AlignToNormal);
+
+        for _ in 0..100 {
+            let transform = value.generate();
+            let up = transform.rotation * Vec3::Y;
+            assert!(up.distance(transform.translation / 2.) < 0.001);
+        }
+
+        let transform = value.generate_on_surface_with(&mut rand::rng(), Vec3::X);
+        assert!((transform.rotation * Vec3::Y).distance(Vec3::X) < 0.001);
+    }
+
+    #[test]
+    fn yaw_keeps_upright_and_scale_in_range() {
+        let value = RandTransform::default()
+            .with_rotation(RandRotation::Yaw)
+            .with_scale(RandScale::Uniform(RandF32::uniform(0.5, 2.)));
+
+        for _ in 0..100 {
+            let transform = value.generate();
+            assert!((transform.rotation * Vec3::Y).distance(Vec3::Y) < 0.001);
+            assert!((0.5..=2.).contains(&transform.scale.x));
+            assert_eq!(transform.scale, Vec3::splat(transform.scale.x));
+        }
+    }
+
+    #[test]
+    fn constant_transform() {
+        let transform = Transform::from_xyz(1., 2., 3.)
+            .with_rotation(Quat::from_rotation_x(0.3))
+            .with_scale(Vec3::new(1., 2., 3.));
+        assert_eq!(RandTransform::constant(transform).generate(), transform);
+    }
+
+    #[test]
+    fn grid_is_centered_and_jittered() {
+        let pattern = SpawnPattern::Grid {
+            counts: UVec3::new(3, 1, 2),
+            spacing: Vec3::splat(2.),
+            jitter: Vec3::new(0.5, 0., 0.5),
+        };
+        let transforms = pattern.transforms();
+        assert_eq!(transforms.len(), 6);
+
+        let center = transforms.iter().map(|t| t.translation).sum::<Vec3>() / 6.;
+        assert!(center.length() < 1.);
+        for transform in transforms {
+            let cell = (transform.translation + Vec3::new(2., 0., 1.)) / 2.;
+            assert!(cell.distance(cell.round()) <= 0.25 * 2f32.sqrt() + 0.001);
+            assert_eq!(transform.translation.y, 0.);
+        }
+    }
+
+    #[test]
+    fn ring_spiral_and_line() {
+        let ring = SpawnPattern::Ring {
+            count: 4,
+            radius: 2.,
+            face_outward: true,
+        }
+        .transforms();
+        assert!(ring[0].translation.distance(Vec3::new(0., 0., -2.)) < 0.001);
+        assert!(ring[1].translation.distance(Vec3::new(-2., 0., 0.)) < 0.001);
+        for transform in &ring {
+            let outward = transform.translation.normalize();
+            assert!(transform.forward().distance(outward) < 0.001);
+        }
+
+        let spiral = SpawnPattern::Spiral {
+            count: 5,
+            start_radius: 1.,
+            end_radius: 3.,
+            turns: 1.,
+            height: 4.,
+            face_outward: false,
+        }
+        .transforms();
+        assert!(spiral[0].translation.distance(Vec3::new(0., 0., -1.)) < 0.001);
+        assert!(spiral[4].translation.distance(Vec3::new(0., 4., -3.)) < 0.001);
+
+        let line = SpawnPattern::Line {
+            count: 3,
+            start: Vec3::ZERO,
+            end: Vec3::X * 4.,
+        }
+        .transforms();
+        assert_eq!(line[1].translation, Vec3::X * 2.);
+    }
+
+    #[test]
+    fn spawner_from_ron() {
+        let spawner: Spawner = ron::from_str(
+            "(
+                pattern: Ring(count: 8, radius: 5.0),
+                variation: (
+                    position: Offset((
+                        magnitude: (min: 0.0, max: 1.0),
+                        direction: (0.0, 1.0, 0.0),
+                        spread: 0.0,
+                    )),
+                    rotation: Yaw,
+                ),
+            )",
+        )
+        .unwrap();
+
+        let transforms = spawner.spawn();
+        assert_eq!(transforms.len(), 8);
+        for transform in transforms {
+            let flat = transform.translation.with_y(0.).length();
+            assert!((flat - 5.).abs() < 0.001);
+            assert!((0.0..=1.).contains(&transform.translation.y));
+        }
+    }
+}
@@ -1,7 +1,17 @@
-use bevy::prelude::*;
+use bevy::{
+    math::{DVec2, DVec3},
+    prelude::*,
+};
 use std::time::Duration;
 
-use crate::prelude::{PitchYaw, PitchYawClamped};
+use super::pose::PoseVelocity;
+use crate::{
+    geometric::{
+        angle::Angle, cylindrical::Cylindrical, dpitchyaw::DPitchYaw,
+        dpitchyawclamped::DPitchYawClamped, spherical::Spherical,
+    },
+    prelude::{PitchYaw, PitchYawClamped},
+};
 
 pub trait TickDerivative {
     type Derivative;
@@ -35,6 +45,36 @@ impl TickDerivative for PitchYaw {
     }
 }
 
+impl TickDerivative for Spherical {
+    /// Yaw (`x`), pitch (`y`) and radius (`z`) rates
+    type Derivative = Vec3;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        let dt = dt.as_secs_f32();
+        Spherical::new(
+            self.y + derivative.x * dt,
+            self.p + derivative.y * dt,
+            self.r + derivative.z * dt,
+        )
+        .normalize()
+    }
+}
+
+impl TickDerivative for Cylindrical {
+    /// Yaw (`x`), height (`y`) and radius (`z`) rates
+    type Derivative = Vec3;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        let dt = dt.as_secs_f32();
+        Cylindrical::new(
+            self.y + derivative.x * dt,
+            self.h + derivative.y * dt,
+            self.r + derivative.z * dt,
+        )
+        .normalize()
+    }
+}
+
 impl TickDerivative for f32 {
     type Derivative = f32;
     fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
@@ -57,3 +97,119 @@ impl TickDerivative for Vec2 {
         *self + derivative * dt.as_secs_f32()
     }
 }
+
+impl TickDerivative for DPitchYawClamped {
+    type Derivative = DVec2;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        DPitchYawClamped::new_with_clamps(
+            self.y + derivative.x * dt.as_secs_f64(),
+            self.p + derivative.y * dt.as_secs_f64(),
+            self.clamp_p,
+            self.clamp_y,
+        )
+        .normalize()
+    }
+}
+
+impl TickDerivative for DPitchYaw {
+    type Derivative = DVec2;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        DPitchYaw::new(
+            self.y + derivative.x * dt.as_secs_f64(),
+            self.p + derivative.y * dt.as_secs_f64(),
+        )
+        .normalize()
+    }
+}
+
+impl TickDerivative for f64 {
+    type Derivative = f64;
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        self + derivative * dt.as_secs_f64()
+    }
+}
+
+impl TickDerivative for DVec3 {
+    type Derivative = DVec3;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        *self + derivative * dt.as_secs_f64()
+    }
+}
+
+impl TickDerivative for DVec2 {
+    type Derivative = DVec2;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        *self + derivative * dt.as_secs_f64()
+    }
+}
+
+impl TickDerivative for Angle {
+    type Derivative = f32;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        Angle(self.0 + derivative * dt.as_secs_f32()).normalize()
+    }
+}
+
+impl TickDerivative for Rot2 {
+    type Derivative = f32;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        (Rot2::radians(derivative * dt.as_secs_f32()) * *self).fast_renormalize()
+    }
+}
+
+impl TickDerivative for Dir3 {
+    /// Angular velocity as a vector tangent to the sphere at the current direction,
+    /// whose length is the angular speed in radians per second
+    type Derivative = Vec3;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        let rotation = Quat::from_scaled_axis(self.cross(derivative) * dt.as_secs_f32());
+        (rotation * *self).fast_renormalize()
+    }
+}
+
+impl TickDerivative for Dir2 {
+    type Derivative = f32;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        (Rot2::radians(derivative * dt.as_secs_f32()) * *self).fast_renormalize()
+    }
+}
+
+impl TickDerivative for Quat {
+    /// Angular velocity, as an axis scaled by the angular speed in radians per second
+    type Derivative = Vec3;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        (Quat::from_scaled_axis(derivative * dt.as_secs_f32()) * *self).normalize()
+    }
+}
+
+impl TickDerivative for Transform {
+    type Derivative = PoseVelocity;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        Transform {
+            translation: self.translation.tick(dt, derivative.linear),
+            rotation: self.rotation.tick(dt, derivative.angular),
+            scale: self.scale.tick(dt, derivative.scale),
+        }
+    }
+}
+
+impl TickDerivative for Isometry3d {
+    type Derivative = PoseVelocity;
+
+    fn tick(&self, dt: Duration, derivative: Self::Derivative) -> Self {
+        Isometry3d::new(
+            Vec3::from(self.translation).tick(dt, derivative.linear),
+            self.rotation.tick(dt, derivative.angular),
+        )
+    }
+}
@@ -1,16 +1,28 @@
 use super::{
-    super::geometric::{pitchyaw::PitchYaw, pitchyawclamped::PitchYawClamped},
+    super::geometric::{
+        angle::Angle, cylindrical::Cylindrical, dpitchyaw::DPitchYaw,
+        dpitchyawclamped::DPitchYawClamped, pitchyaw::PitchYaw, pitchyawclamped::PitchYawClamped,
+        spherical::Spherical,
+    },
     core::TickInterpolator,
+    pose::PoseSpeed,
+};
+use bevy::{
+    math::{DQuat, DVec3, Dir2, Dir3, Isometry3d, Quat, Rot2, Vec2, Vec3},
+    transform::components::Transform,
 };
-use bevy::math::{Quat, Vec3};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Moves `current` towards `target` at a constant `speed`.
+///
+/// `speed` is usually a single `f32`, but some types also support a per-axis speed,
+/// e.g. `LinearStepper<PitchYaw, Vec2>` takes separate yaw (`x`) and pitch (`y`) speeds.
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
-pub struct LinearStepper<T> {
+pub struct LinearStepper<T, S = f32> {
     pub current: T,
     pub target: T,
-    pub speed: f32,
+    pub speed: S,
 }
 
 // --- Concrete implementations
@@ -26,6 +38,30 @@ impl<T: Clone> LinearStepper<T> {
     }
 }
 
+impl<T: Clone, S> LinearStepper<T, S> {
+    pub fn new_with_speed(value: T, speed: S) -> Self {
+        Self {
+            current: value.clone(),
+            target: value,
+            speed,
+        }
+    }
+
+    /// Converts the current and target values and the speed, e.g. between `f32` and
+    /// `f64` types
+    pub fn map_values<U, R>(
+        self,
+        f: impl Fn(T) -> U,
+        f_speed: impl FnOnce(S) -> R,
+    ) -> LinearStepper<U, R> {
+        LinearStepper {
+            current: f(self.current),
+            target: f(self.target),
+            speed: f_speed(self.speed),
+        }
+    }
+}
+
 impl TickInterpolator<f32> for LinearStepper<f32> {
     fn tick(&mut self, dt: Duration) {
         let delta_abs = self.speed * dt.as_secs_f32();
@@ -119,4 +155,356 @@ impl TickInterpolator<PitchYawClamped> for LinearStepper<PitchYawClamped> {
         self.current
     }
 }
+
+impl TickInterpolator<PitchYaw> for LinearStepper<PitchYaw, Vec2> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward_per_axis(self.target, self.speed * dt.as_secs_f32());
+    }
+
+    fn set_target(&mut self, target: PitchYaw) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> PitchYaw {
+        self.current
+    }
+}
+
+impl TickInterpolator<PitchYawClamped> for LinearStepper<PitchYawClamped, Vec2> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward_per_axis(self.target, self.speed * dt.as_secs_f32());
+    }
+
+    fn set_target(&mut self, target: PitchYawClamped) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> PitchYawClamped {
+        self.current
+    }
+}
+
+impl TickInterpolator<Spherical> for LinearStepper<Spherical, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward_per_axis(self.target, self.speed * dt.as_secs_f32());
+    }
+
+    fn set_target(&mut self, target: Spherical) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> Spherical {
+        self.current
+    }
+}
+
+impl TickInterpolator<Cylindrical> for LinearStepper<Cylindrical, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward_per_axis(self.target, self.speed * dt.as_secs_f32());
+    }
+
+    fn set_target(&mut self, target: Cylindrical) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> Cylindrical {
+        self.current
+    }
+}
+
+impl TickInterpolator<Angle> for LinearStepper<Angle> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward(self.target, self.speed * dt.as_secs_f32());
+    }
+
+    fn set_target(&mut self, target: Angle) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> Angle {
+        self.current
+    }
+}
+
+impl TickInterpolator<Rot2> for LinearStepper<Rot2> {
+    fn tick(&mut self, dt: Duration) {
+        let delta_angle = self.current.angle_to(self.target);
+        let max_delta_angle = self.speed * dt.as_secs_f32();
+        if delta_angle.abs() < max_delta_angle {
+            self.current = self.target;
+        } else {
+            self.current = Rot2::radians(max_delta_angle * delta_angle.signum()) * self.current;
+        }
+    }
+
+    fn set_target(&mut self, target: Rot2) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Rot2 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Dir3> for LinearStepper<Dir3> {
+    fn tick(&mut self, dt: Duration) {
+        let delta_angle = self.current.angle_between(*self.target);
+        let max_delta_angle = self.speed * dt.as_secs_f32();
+        if delta_angle < max_delta_angle {
+            self.current = self.target;
+        } else if delta_angle > 0. {
+            let ratio = max_delta_angle / delta_angle;
+            self.current = self.current.slerp(self.target, ratio);
+        }
+    }
+
+    fn set_target(&mut self, target: Dir3) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Dir3 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Dir2> for LinearStepper<Dir2> {
+    fn tick(&mut self, dt: Duration) {
+        let delta_angle = self.current.angle_to(*self.target);
+        let max_delta_angle = self.speed * dt.as_secs_f32();
+        if delta_angle.abs() < max_delta_angle {
+            self.current = self.target;
+        } else {
+            self.current = Rot2::radians(max_delta_angle * delta_angle.signum()) * self.current;
+        }
+    }
+
+    fn set_target(&mut self, target: Dir2) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Dir2 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Transform> for LinearStepper<Transform, PoseSpeed> {
+    fn tick(&mut self, dt: Duration) {
+        let distances = Vec3::new(
+            self.current.translation.distance(self.target.translation),
+            self.current.rotation.angle_between(self.target.rotation),
+            self.current.scale.distance(self.target.scale),
+        );
+        let fractions = self.speed.step_fractions(distances, dt.as_secs_f32());
+
+        self.current = Transform {
+            translation: self
+                .current
+                .translation
+                .lerp(self.target.translation, fractions.x),
+            rotation: self
+                .current
+                .rotation
+                .slerp(self.target.rotation, fractions.y),
+            scale: self.current.scale.lerp(self.target.scale, fractions.z),
+        };
+    }
+
+    fn set_target(&mut self, target: Transform) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Transform {
+        self.current
+    }
+}
+
+impl TickInterpolator<Isometry3d> for LinearStepper<Isometry3d, PoseSpeed> {
+    fn tick(&mut self, dt: Duration) {
+        let distances = Vec3::new(
+            self.current.translation.distance(self.target.translation),
+            self.current.rotation.angle_between(self.target.rotation),
+            0.,
+        );
+        let fractions = self.speed.step_fractions(distances, dt.as_secs_f32());
+
+        self.current = Isometry3d {
+            translation: self
+                .current
+                .translation
+                .lerp(self.target.translation, fractions.x),
+            rotation: self
+                .current
+                .rotation
+                .slerp(self.target.rotation, fractions.y),
+        };
+    }
+
+    fn set_target(&mut self, target: Isometry3d) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Isometry3d {
+        self.current
+    }
+}
+
+impl TickInterpolator<f64> for LinearStepper<f64, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let delta_abs = self.speed * dt.as_secs_f64();
+        if (self.target - self.current).abs() > delta_abs {
+            self.current += (self.target - self.current).signum() * delta_abs;
+        } else {
+            self.current = self.target;
+        }
+    }
+
+    fn set_target(&mut self, target: f64) {
+        self.target = target;
+    }
+
+    fn get(&self) -> f64 {
+        self.current
+    }
+}
+
+impl TickInterpolator<DVec3> for LinearStepper<DVec3, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let delta = self.target - self.current;
+        let delta_length = delta.length();
+        let max_delta = self.speed * dt.as_secs_f64();
+        if delta_length < max_delta {
+            self.current = self.target;
+        } else if delta_length > 0. {
+            let delta = delta.normalize() * max_delta;
+            self.current += delta;
+        }
+    }
+
+    fn set_target(&mut self, target: DVec3) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DVec3 {
+        self.current
+    }
+}
+
+impl TickInterpolator<DQuat> for LinearStepper<DQuat, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let delta_angle = self.current.angle_between(self.target);
+        let max_delta_angle = self.speed * dt.as_secs_f64();
+        if delta_angle < max_delta_angle {
+            self.current = self.target;
+        } else if delta_angle > 0. {
+            let ratio = max_delta_angle / delta_angle;
+            self.current = self.current.slerp(self.target, ratio);
+        }
+    }
+
+    fn set_target(&mut self, target: DQuat) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DQuat {
+        self.current
+    }
+}
+
+impl TickInterpolator<DPitchYaw> for LinearStepper<DPitchYaw, f64> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward(self.target, self.speed * dt.as_secs_f64());
+    }
+
+    fn set_target(&mut self, target: DPitchYaw) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> DPitchYaw {
+        self.current
+    }
+}
+
+impl TickInterpolator<DPitchYawClamped> for LinearStepper<DPitchYawClamped, f64> {
+    fn tick(&mut self, dt: Duration) {
+        self.current = self
+            .current
+            .step_toward(self.target, self.speed * dt.as_secs_f64());
+    }
+
+    fn set_target(&mut self, target: DPitchYawClamped) {
+        self.target = target.normalize();
+    }
+
+    fn get(&self) -> DPitchYawClamped {
+        self.current
+    }
+}
+
 // -------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steppers::pose::PoseRates;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn dir3_moves_at_constant_angular_speed() {
+        let mut stepper = LinearStepper::new(Dir3::NEG_Z, 0.5);
+        stepper.set_target(Dir3::X);
+        stepper.tick(Duration::from_secs(1));
+
+        let angle = stepper.get().angle_between(Vec3::NEG_Z);
+        assert!((angle - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn synchronized_transform_components_arrive_together() {
+        let speed = PoseSpeed::Synchronized(PoseRates::new(1., 1., 1.));
+        let mut stepper = LinearStepper::new_with_speed(Transform::IDENTITY, speed);
+        stepper
+            .set_target(Transform::from_xyz(4., 0., 0.).with_rotation(Quat::from_rotation_y(1.)));
+
+        stepper.tick(Duration::from_secs(2));
+        let halfway = stepper.get();
+        assert!((halfway.translation.x - 2.).abs() < EPSILON);
+        assert!((halfway.rotation.angle_between(Quat::IDENTITY) - 0.5).abs() < EPSILON);
+
+        stepper.tick(Duration::from_secs(2));
+        assert_eq!(stepper.get(), stepper.target);
+    }
+
+    #[test]
+    fn f64_stepper_keeps_precision_far_from_origin() {
+        let start = DVec3::new(1e9, 0., 0.);
+        let mut stepper = LinearStepper::new_with_speed(start, 1e-3);
+        stepper.set_target(start + DVec3::X);
+        stepper.tick(Duration::from_secs(1));
+
+        assert!((stepper.get().x - (1e9 + 1e-3)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn map_values_converts_speed() {
+        let stepper =
+            LinearStepper::new(PitchYaw::new(0.5, 0.), 2.).map_values(DPitchYaw::from, f64::from);
+        let mut stepper: LinearStepper<DPitchYaw, f64> = stepper;
+        stepper.set_target(DPitchYaw::new(-1., 0.));
+        stepper.tick(Duration::from_millis(500));
+
+        assert!((stepper.get().y + 0.5).abs() < 1e-9);
+    }
+}
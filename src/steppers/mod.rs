@@ -1,4 +1,5 @@
 pub mod core;
 pub mod derivatives;
 pub mod linear_stepper;
+pub mod pose;
 pub mod spring_stepper;
@@ -0,0 +1,134 @@
+use bevy::math::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Per-component rates for pose steppers.
+///
+/// Used as speeds by `LinearStepper` (units and radians per second), and as spring or
+/// damping coefficients by `SpringStepper`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoseRates {
+    pub translation: f32,
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+impl PoseRates {
+    pub fn new(translation: f32, rotation: f32, scale: f32) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn splat(rate: f32) -> Self {
+        Self::new(rate, rate, rate)
+    }
+}
+
+/// Speed of a `LinearStepper` over a pose
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PoseSpeed {
+    /// Each component moves at its own speed and arrives on its own
+    Independent(PoseRates),
+    /// Components move at most at their own speed, but the faster ones are slowed down
+    /// so that all of them arrive at the same time. As in `Independent`, components with
+    /// a zero speed stay where they are, and the others arrive together without them
+    Synchronized(PoseRates),
+}
+
+impl Default for PoseSpeed {
+    fn default() -> Self {
+        Self::Synchronized(PoseRates::splat(1.))
+    }
+}
+
+impl PoseSpeed {
+    /// Fraction of the remaining translation, rotation and scale distances to cover
+    /// during a step of `dt` seconds
+    pub(crate) fn step_fractions(&self, distances: Vec3, dt: f32) -> Vec3 {
+        let (PoseSpeed::Independent(rates) | PoseSpeed::Synchronized(rates)) = self;
+        let speeds = Vec3::new(rates.translation, rates.rotation, rates.scale);
+        let fraction = |distance: f32, step: f32| {
+            if distance <= step || distance == 0. {
+                1.
+            } else {
+                step / distance
+            }
+        };
+
+        match self {
+            PoseSpeed::Independent(_) => Vec3::new(
+                fraction(distances.x, speeds.x * dt),
+                fraction(distances.y, speeds.y * dt),
+                fraction(distances.z, speeds.z * dt),
+            ),
+            PoseSpeed::Synchronized(_) => {
+                // Components with nothing left to cover give NaN here, and those with a
+                // zero speed infinity. The latter are frozen, so neither sets the pace
+                let time_left = (distances / speeds)
+                    .to_array()
+                    .into_iter()
+                    .filter(|time| time.is_finite())
+                    .fold(0., f32::max);
+                let frozen = speeds.cmpeq(Vec3::ZERO) & distances.cmpgt(Vec3::ZERO);
+                Vec3::select(frozen, Vec3::ZERO, Vec3::splat(fraction(time_left, dt)))
+            }
+        }
+    }
+}
+
+/// Velocity of a `SpringStepper` over a pose
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoseVelocity {
+    pub linear: Vec3,
+    /// Angular velocity, as an axis scaled by the angular speed in radians per second
+    pub angular: Vec3,
+    pub scale: Vec3,
+}
+
+/// Shortest rotation from `from` to `to`, as an axis scaled by its angle
+pub(crate) fn rotation_error(from: Quat, to: Quat) -> Vec3 {
+    let delta = to * from.inverse();
+    let delta = if delta.w < 0. { -delta } else { delta };
+    delta.to_scaled_axis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn synchronized_components_share_arrival() {
+        let speed = PoseSpeed::Synchronized(PoseRates::new(1., 1., 1.));
+        let fractions = speed.step_fractions(Vec3::new(4., 1., 0.), 1.);
+
+        assert!((fractions - Vec3::splat(0.25)).length() < EPSILON);
+    }
+
+    #[test]
+    fn independent_components_arrive_on_their_own() {
+        let speed = PoseSpeed::Independent(PoseRates::new(1., 1., 1.));
+        let fractions = speed.step_fractions(Vec3::new(4., 1., 0.), 1.);
+
+        assert!((fractions - Vec3::new(0.25, 1., 1.)).length() < EPSILON);
+    }
+
+    #[test]
+    fn synchronized_zero_speed_stays_frozen() {
+        let speed = PoseSpeed::Synchronized(PoseRates::new(1., 0., 1.));
+        let fractions = speed.step_fractions(Vec3::new(4., 1., 0.), 1.);
+
+        assert!((fractions - Vec3::new(0.25, 0., 0.25)).length() < EPSILON);
+    }
+
+    #[test]
+    fn synchronized_all_zero_speeds_stay_frozen() {
+        let speed = PoseSpeed::Synchronized(PoseRates::splat(0.));
+        let fractions = speed.step_fractions(Vec3::new(4., 1., 0.), 1.);
+
+        assert_eq!(fractions, Vec3::new(0., 0., 1.));
+    }
+}
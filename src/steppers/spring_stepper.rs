@@ -1,27 +1,35 @@
 use crate::prelude::PitchYaw;
 
 use super::{
-    super::geometric::pitchyawclamped::PitchYawClamped, core::TickInterpolator,
+    super::geometric::{
+        angle::Angle, cylindrical::Cylindrical, dpitchyaw::DPitchYaw,
+        dpitchyawclamped::DPitchYawClamped, pitchyawclamped::PitchYawClamped, spherical::Spherical,
+    },
+    core::TickInterpolator,
     derivatives::TickDerivative,
+    pose::{PoseRates, PoseVelocity, rotation_error},
+};
+use bevy::{
+    math::{DVec2, DVec3, Dir2, Dir3, Isometry3d, Quat, Rot2, Vec2, Vec3},
+    transform::components::Transform,
 };
-use bevy::math::{Vec2, Vec3};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{ops::Add, time::Duration};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
-pub struct SpringStepper<T, D = T> {
+pub struct SpringStepper<T, D = T, K = f32> {
     pub current: T,
     pub target: T,
     pub velocity: D,
-    pub spring: f32,
-    pub damping: f32,
+    pub spring: K,
+    pub damping: K,
 }
 
 pub const SPRING_MASS: f32 = 1.;
 
 impl<T, D> SpringStepper<T, D>
 where
-    T: TickDerivative<Derivative = D> + Clone + Default,
+    T: TickDerivative<Derivative = D> + Clone,
     D: Serialize + DeserializeOwned + Default,
 {
     pub fn new(value: T, spring: f32, damping: f32) -> Self {
@@ -52,6 +60,43 @@ where
     }
 }
 
+impl<T, D, K> SpringStepper<T, D, K>
+where
+    T: TickDerivative<Derivative = D> + Clone,
+    D: Serialize + DeserializeOwned + Default,
+{
+    /// Like `new`, but with coefficients other than a single `f32`, e.g. [`PoseRates`]
+    /// to tune each component of a pose separately
+    pub fn new_with_coefficients(value: T, spring: K, damping: K) -> Self {
+        Self {
+            current: value.clone(),
+            target: value,
+            velocity: D::default(),
+            spring,
+            damping,
+        }
+    }
+}
+
+impl<T, D, K> SpringStepper<T, D, K> {
+    /// Converts the current and target values, the velocity and the coefficients, e.g.
+    /// between `f32` and `f64` types
+    pub fn map_values<U, E, L>(
+        self,
+        f: impl Fn(T) -> U,
+        f_velocity: impl FnOnce(D) -> E,
+        f_coefficient: impl Fn(K) -> L,
+    ) -> SpringStepper<U, E, L> {
+        SpringStepper {
+            current: f(self.current),
+            target: f(self.target),
+            velocity: f_velocity(self.velocity),
+            spring: f_coefficient(self.spring),
+            damping: f_coefficient(self.damping),
+        }
+    }
+}
+
 // --- Concrete implementations
 // -------------------------------------------------------------------------------
 
@@ -142,6 +187,340 @@ impl TickInterpolator<Vec2> for SpringStepper<Vec2> {
     }
 }
 
+impl TickInterpolator<Spherical> for SpringStepper<Spherical, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        let spring_force = Vec3::new(spring_force.y, spring_force.p, spring_force.r);
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Spherical) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Spherical {
+        self.current
+    }
+}
+
+impl TickInterpolator<Cylindrical> for SpringStepper<Cylindrical, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        let spring_force = Vec3::new(spring_force.y, spring_force.h, spring_force.r);
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Cylindrical) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Cylindrical {
+        self.current
+    }
+}
+
+impl TickInterpolator<Angle> for SpringStepper<Angle, f32> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current).0 * self.spring;
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Angle) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Angle {
+        self.current
+    }
+}
+
+impl TickInterpolator<Rot2> for SpringStepper<Rot2, f32> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = self.current.angle_to(self.target) * self.spring;
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Rot2) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Rot2 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Dir3> for SpringStepper<Dir3, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        let current = *self.current;
+        let target = *self.target;
+
+        // Tangent vector pointing towards the target, as long as the angle to it
+        let axis = current.cross(target);
+        let angle = axis.length().atan2(current.dot(target));
+        let towards = if axis.length_squared() > 1e-12 {
+            axis.cross(current).normalize()
+        } else if angle > 0. {
+            // Target is opposite, any direction is as good as any other
+            current.any_orthonormal_vector()
+        } else {
+            Vec3::ZERO
+        };
+
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = towards * angle * self.spring;
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.velocity = self.velocity.reject_from_normalized(current);
+
+        let next = self.current.tick(dt, self.velocity);
+        // Carry the velocity along to stay tangent at the new direction
+        let transport = Quat::from_rotation_arc(current, *next);
+        self.velocity = (transport * self.velocity).reject_from_normalized(*next);
+        self.current = next;
+    }
+
+    fn set_target(&mut self, target: Dir3) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Dir3 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Dir2> for SpringStepper<Dir2, f32> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = self.current.angle_to(*self.target) * self.spring;
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Dir2) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Dir2 {
+        self.current
+    }
+}
+
+impl TickInterpolator<Quat> for SpringStepper<Quat, Vec3> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = rotation_error(self.current, self.target) * self.spring;
+        self.velocity += (damping_force + spring_force) * (dt.as_secs_f32() / SPRING_MASS);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Quat) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Quat {
+        self.current
+    }
+}
+
+/// Accelerates a pose velocity towards the target, with one coefficient per component
+fn pose_spring_step(
+    velocity: &mut PoseVelocity,
+    errors: PoseVelocity,
+    spring: PoseRates,
+    damping: PoseRates,
+    dt: Duration,
+) {
+    let dt = dt.as_secs_f32() / SPRING_MASS;
+    velocity.linear +=
+        (velocity.linear * (-damping.translation) + errors.linear * spring.translation) * dt;
+    velocity.angular +=
+        (velocity.angular * (-damping.rotation) + errors.angular * spring.rotation) * dt;
+    velocity.scale += (velocity.scale * (-damping.scale) + errors.scale * spring.scale) * dt;
+}
+
+fn transform_errors(current: &Transform, target: &Transform) -> PoseVelocity {
+    PoseVelocity {
+        linear: target.translation - current.translation,
+        angular: rotation_error(current.rotation, target.rotation),
+        scale: target.scale - current.scale,
+    }
+}
+
+fn isometry_errors(current: &Isometry3d, target: &Isometry3d) -> PoseVelocity {
+    PoseVelocity {
+        linear: (target.translation - current.translation).into(),
+        angular: rotation_error(current.rotation, target.rotation),
+        scale: Vec3::ZERO,
+    }
+}
+
+impl TickInterpolator<Transform> for SpringStepper<Transform, PoseVelocity> {
+    fn tick(&mut self, dt: Duration) {
+        let errors = transform_errors(&self.current, &self.target);
+        let spring = PoseRates::splat(self.spring);
+        let damping = PoseRates::splat(self.damping);
+        pose_spring_step(&mut self.velocity, errors, spring, damping, dt);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Transform) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Transform {
+        self.current
+    }
+}
+
+impl TickInterpolator<Transform> for SpringStepper<Transform, PoseVelocity, PoseRates> {
+    fn tick(&mut self, dt: Duration) {
+        let errors = transform_errors(&self.current, &self.target);
+        pose_spring_step(&mut self.velocity, errors, self.spring, self.damping, dt);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Transform) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Transform {
+        self.current
+    }
+}
+
+impl TickInterpolator<Isometry3d> for SpringStepper<Isometry3d, PoseVelocity> {
+    fn tick(&mut self, dt: Duration) {
+        let errors = isometry_errors(&self.current, &self.target);
+        let spring = PoseRates::splat(self.spring);
+        let damping = PoseRates::splat(self.damping);
+        pose_spring_step(&mut self.velocity, errors, spring, damping, dt);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Isometry3d) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Isometry3d {
+        self.current
+    }
+}
+
+impl TickInterpolator<Isometry3d> for SpringStepper<Isometry3d, PoseVelocity, PoseRates> {
+    fn tick(&mut self, dt: Duration) {
+        let errors = isometry_errors(&self.current, &self.target);
+        pose_spring_step(&mut self.velocity, errors, self.spring, self.damping, dt);
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: Isometry3d) {
+        self.target = target;
+    }
+
+    fn get(&self) -> Isometry3d {
+        self.current
+    }
+}
+
+impl TickInterpolator<DPitchYawClamped> for SpringStepper<DPitchYawClamped, DVec2, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        let spring_force = DVec2::new(spring_force.y, spring_force.p);
+        self.velocity +=
+            (damping_force + spring_force) * (dt.as_secs_f64() / f64::from(SPRING_MASS));
+        self.current = self.current.tick(dt, self.velocity)
+    }
+
+    fn set_target(&mut self, target: DPitchYawClamped) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DPitchYawClamped {
+        self.current
+    }
+}
+
+impl TickInterpolator<DPitchYaw> for SpringStepper<DPitchYaw, DVec2, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        let spring_force = DVec2::new(spring_force.y, spring_force.p);
+        self.velocity +=
+            (damping_force + spring_force) * (dt.as_secs_f64() / f64::from(SPRING_MASS));
+        self.current = self.current.tick(dt, self.velocity)
+    }
+
+    fn set_target(&mut self, target: DPitchYaw) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DPitchYaw {
+        self.current
+    }
+}
+
+impl TickInterpolator<f64> for SpringStepper<f64, f64, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        self.velocity +=
+            (damping_force + spring_force) * (dt.as_secs_f64() / f64::from(SPRING_MASS));
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: f64) {
+        self.target = target;
+    }
+
+    fn get(&self) -> f64 {
+        self.current
+    }
+}
+
+impl TickInterpolator<DVec3> for SpringStepper<DVec3, DVec3, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        self.velocity +=
+            (damping_force + spring_force) * (dt.as_secs_f64() / f64::from(SPRING_MASS));
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: DVec3) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DVec3 {
+        self.current
+    }
+}
+
+impl TickInterpolator<DVec2> for SpringStepper<DVec2, DVec2, f64> {
+    fn tick(&mut self, dt: Duration) {
+        let damping_force = self.velocity * (-self.damping);
+        let spring_force = (self.target - self.current) * self.spring;
+        self.velocity +=
+            (damping_force + spring_force) * (dt.as_secs_f64() / f64::from(SPRING_MASS));
+        self.current = self.current.tick(dt, self.velocity);
+    }
+
+    fn set_target(&mut self, target: DVec2) {
+        self.target = target;
+    }
+
+    fn get(&self) -> DVec2 {
+        self.current
+    }
+}
+
 // -------------------------------------------------------------------------------
 
 // --- Utility functions
@@ -154,3 +533,84 @@ pub fn critical_damp_coeff(spring_constant: f32) -> f32 {
 }
 
 // -------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::math::EulerRot;
+
+    const EPSILON: f32 = 0.0001;
+
+    #[test]
+    fn dir3_spring_follows_great_circle() {
+        let from = Dir3::X;
+        let to = Dir3::Y;
+        let mut stepper = SpringStepper::new(from, 40., critical_damp_coeff(40.));
+        stepper.set_target(to);
+
+        for _ in 0..300 {
+            stepper.tick(Duration::from_millis(10));
+            let dir = stepper.get();
+            assert!((dir.length() - 1.).abs() < EPSILON);
+            assert!(dir.z.abs() < EPSILON, "Left the great circle: {dir:?}");
+        }
+        assert!(stepper.get().distance(*to) < 0.001);
+    }
+
+    #[test]
+    fn quat_spring_settles_on_target() {
+        let target = Quat::from_euler(EulerRot::YXZ, 2.5, -0.4, 0.3);
+        let mut stepper = SpringStepper::new(Quat::IDENTITY, 40., critical_damp_coeff(40.));
+        stepper.set_target(target);
+
+        for _ in 0..300 {
+            stepper.tick(Duration::from_millis(10));
+            assert!(stepper.get().is_normalized());
+        }
+        assert!(stepper.get().angle_between(target) < 0.001);
+    }
+
+    #[test]
+    fn transform_spring_with_independent_coefficients() {
+        let target = Transform::from_xyz(3., -1., 2.)
+            .with_rotation(Quat::from_rotation_z(1.))
+            .with_scale(Vec3::splat(2.));
+        let spring = PoseRates::new(40., 400., 40.);
+        let damping = PoseRates::new(
+            critical_damp_coeff(40.),
+            critical_damp_coeff(400.),
+            critical_damp_coeff(40.),
+        );
+        let mut stepper =
+            SpringStepper::new_with_coefficients(Transform::IDENTITY, spring, damping);
+        stepper.set_target(target);
+
+        stepper.tick(Duration::from_millis(100));
+        let early = stepper.get();
+        // The stiffer rotation gets further along than the translation
+        let rotation_progress = early.rotation.angle_between(Quat::IDENTITY);
+        let translation_progress = early.translation.length() / target.translation.length();
+        assert!(rotation_progress > translation_progress);
+
+        for _ in 0..300 {
+            stepper.tick(Duration::from_millis(10));
+        }
+        let settled = stepper.get();
+        assert!(settled.translation.distance(target.translation) < 0.001);
+        assert!(settled.rotation.angle_between(target.rotation) < 0.001);
+        assert!(settled.scale.distance(target.scale) < 0.001);
+    }
+
+    #[test]
+    fn f64_spring_settles_on_target() {
+        let spring = 40.;
+        let damping = f64::from(critical_damp_coeff(40.));
+        let mut stepper = SpringStepper::new_with_coefficients(DVec3::splat(1e9), spring, damping);
+        stepper.set_target(DVec3::splat(1e9 + 1.));
+
+        for _ in 0..200 {
+            stepper.tick(Duration::from_millis(10));
+        }
+        assert!((stepper.get() - DVec3::splat(1e9 + 1.)).length() < 1e-3);
+    }
+}